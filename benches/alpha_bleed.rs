@@ -0,0 +1,64 @@
+#[path = "../tests/support/reference_bleed.rs"]
+mod reference;
+
+use std::path::PathBuf;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use image::DynamicImage;
+
+fn bench_alpha_bleed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("alpha_bleed");
+    group.sample_size(10);
+
+    for size in [256, 1024, 2048] {
+        let img = DynamicImage::ImageRgba8(reference::sample_image(size, size, 42));
+
+        group.bench_with_input(BenchmarkId::new("buffer", size), &img, |b, img| {
+            b.iter_batched_ref(
+                || img.clone(),
+                rbxsync::alpha_bleed::alpha_bleed,
+                criterion::BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("reference", size), &img, |b, img| {
+            b.iter_batched_ref(
+                || img.clone(),
+                reference::alpha_bleed,
+                criterion::BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_process_icons(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let paths: Vec<PathBuf> = (0..16)
+        .map(|i| {
+            let path = dir.path().join(format!("icon{i}.png"));
+            reference::sample_image(512, 512, i).save(&path).unwrap();
+            path
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("process_icons");
+    group.sample_size(10);
+
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            paths
+                .iter()
+                .map(|p| rbxsync::icon::process_icon(p, true).unwrap())
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| rbxsync::icon::process_icons(&paths, true).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_alpha_bleed, bench_process_icons);
+criterion_main!(benches);
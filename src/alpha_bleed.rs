@@ -4,99 +4,130 @@
 //!
 //! Adapted from Asphalt (https://github.com/jackTabsCode/asphalt).
 
-use std::collections::VecDeque;
-
-use bit_vec::BitVec;
-use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
+use image::{DynamicImage, GenericImage, RgbaImage};
 
 pub fn alpha_bleed(img: &mut DynamicImage) {
-    let (w, h) = img.dimensions();
+    if !img.color().has_alpha() {
+        // Every pixel is opaque, nothing to bleed into.
+        return;
+    }
 
-    let mut can_be_sampled = Mask2::new(w, h);
-    let mut visited = Mask2::new(w, h);
-    let mut to_visit = VecDeque::new();
+    if let DynamicImage::ImageRgba8(rgba) = img {
+        alpha_bleed_rgba8(rgba);
+        return;
+    }
 
-    let adjacent_positions = |x, y| {
-        DIRECTIONS.iter().filter_map(move |(x_offset, y_offset)| {
-            let x_source = (x as i32) + x_offset;
-            let y_source = (y as i32) + y_offset;
+    // Other formats with alpha: bleed an RGBA8 copy and write back only the
+    // pixels the bleed filled, preserving the original pixel format and the
+    // full precision of every other pixel.
+    let mut rgba = img.to_rgba8();
+    let width = rgba.width() as usize;
+    let mut filled = Vec::new();
+    bleed(&mut rgba, |i| filled.push(i));
+    for i in filled {
+        let (x, y) = ((i % width) as u32, (i / width) as u32);
+        img.put_pixel(x, y, *rgba.get_pixel(x, y));
+    }
+}
 
-            if x_source < 0 || y_source < 0 || x_source >= w as i32 || y_source >= h as i32 {
-                return None;
-            }
+/// Alpha bleed working directly on an RGBA8 pixel buffer.
+///
+/// Transparent pixels are filled in breadth-first layers: each layer takes the
+/// average color of its neighbors from previous layers (or opaque pixels).
+pub fn alpha_bleed_rgba8(img: &mut RgbaImage) {
+    bleed(img, |_| {});
+}
+
+/// Runs the bleed, calling `on_fill` with the index of every pixel it fills.
+fn bleed(img: &mut RgbaImage, mut on_fill: impl FnMut(usize)) {
+    let (w, h) = img.dimensions();
+    let (w, h) = (w as usize, h as usize);
+    if w == 0 || h == 0 {
+        return;
+    }
+    let buf: &mut [u8] = img;
 
-            Some((x_source as u32, y_source as u32))
-        })
-    };
+    // Track state on a grid padded by one cell on each side so neighbor
+    // lookups never need bounds checks. Padding cells are marked visited and
+    // are never sampled.
+    let pw = w + 2;
+    let mut state = vec![VISITED; pw * (h + 2)];
+    let grid_offsets = DIRECTIONS.map(|(dx, dy)| dy * pw as isize + dx);
+    let pixel_offsets = DIRECTIONS.map(|(dx, dy)| dy * w as isize + dx);
 
     for y in 0..h {
         for x in 0..w {
-            let pixel = img.get_pixel(x, y);
+            let alpha = buf[(y * w + x) * 4 + 3];
+            state[(y + 1) * pw + x + 1] = if alpha != 0 { SAMPLED } else { UNVISITED };
+        }
+    }
 
-            if pixel[3] != 0 {
-                can_be_sampled.set(x, y);
-                visited.set(x, y);
+    // (grid index, pixel index) of every pixel in the current layer
+    let mut layer = Vec::new();
+    for y in 0..h {
+        for x in 0..w {
+            let g = (y + 1) * pw + x + 1;
+            if state[g] != UNVISITED {
                 continue;
             }
 
-            let borders_opaque = adjacent_positions(x, y).any(|(x_source, y_source)| {
-                let source = img.get_pixel(x_source, y_source);
-                source[3] != 0
-            });
-
+            let borders_opaque = grid_offsets
+                .iter()
+                .any(|&off| state[g.wrapping_add_signed(off)] == SAMPLED);
             if borders_opaque {
-                visited.set(x, y);
-                to_visit.push_back((x, y));
+                state[g] = VISITED;
+                layer.push((g, y * w + x));
             }
         }
     }
 
-    loop {
-        let queue_length = to_visit.len();
-        if queue_length == 0 {
-            break;
-        }
-
-        let mut mutated_coords: Vec<(u32, u32)> = Vec::with_capacity(queue_length);
-
-        for _ in 0..queue_length {
-            if let Some((x, y)) = to_visit.pop_front() {
-                let mut new_color = (0u16, 0u16, 0u16);
-                let mut contributing = 0u16;
-
-                for (x_source, y_source) in adjacent_positions(x, y) {
-                    if can_be_sampled.get(x_source, y_source) {
-                        let source = img.get_pixel(x_source, y_source);
+    let mut next = Vec::new();
+    while !layer.is_empty() {
+        for &(g, i) in &layer {
+            let mut new_color = (0u16, 0u16, 0u16);
+            let mut contributing = 0u16;
+
+            for (&grid_off, &pixel_off) in grid_offsets.iter().zip(&pixel_offsets) {
+                let n = g.wrapping_add_signed(grid_off);
+                match state[n] {
+                    SAMPLED => {
+                        let source = i.wrapping_add_signed(pixel_off) * 4;
                         contributing += 1;
-                        new_color.0 += source[0] as u16;
-                        new_color.1 += source[1] as u16;
-                        new_color.2 += source[2] as u16;
-                    } else if !visited.get(x_source, y_source) {
-                        visited.set(x_source, y_source);
-                        to_visit.push_back((x_source, y_source));
+                        new_color.0 += buf[source] as u16;
+                        new_color.1 += buf[source + 1] as u16;
+                        new_color.2 += buf[source + 2] as u16;
+                    }
+                    UNVISITED => {
+                        state[n] = VISITED;
+                        next.push((n, i.wrapping_add_signed(pixel_off)));
                     }
+                    _ => {}
                 }
-
-                let denominator = u16::max(1, contributing);
-                let pixel = Rgba([
-                    (new_color.0 / denominator) as u8,
-                    (new_color.1 / denominator) as u8,
-                    (new_color.2 / denominator) as u8,
-                    0,
-                ]);
-
-                img.put_pixel(x, y, pixel);
-                mutated_coords.push((x, y));
             }
+
+            let denominator = u16::max(1, contributing);
+            let pixel = &mut buf[i * 4..i * 4 + 4];
+            pixel[0] = (new_color.0 / denominator) as u8;
+            pixel[1] = (new_color.1 / denominator) as u8;
+            pixel[2] = (new_color.2 / denominator) as u8;
+            pixel[3] = 0;
+            on_fill(i);
         }
 
-        for (x, y) in mutated_coords {
-            can_be_sampled.set(x, y);
+        for &(g, _) in &layer {
+            state[g] = SAMPLED;
         }
+
+        layer.clear();
+        std::mem::swap(&mut layer, &mut next);
     }
 }
 
-const DIRECTIONS: &[(i32, i32)] = &[
+const UNVISITED: u8 = 0;
+const VISITED: u8 = 1;
+const SAMPLED: u8 = 2;
+
+const DIRECTIONS: [(isize, isize); 8] = [
     (1, 0),
     (1, 1),
     (0, 1),
@@ -106,27 +137,3 @@ const DIRECTIONS: &[(i32, i32)] = &[
     (0, -1),
     (1, -1),
 ];
-
-struct Mask2 {
-    size: (u32, u32),
-    data: BitVec,
-}
-
-impl Mask2 {
-    fn new(w: u32, h: u32) -> Self {
-        Self {
-            size: (w, h),
-            data: BitVec::from_elem((w * h) as usize, false),
-        }
-    }
-
-    fn get(&self, x: u32, y: u32) -> bool {
-        let index = x + y * self.size.0;
-        self.data.get(index as usize).unwrap_or(false)
-    }
-
-    fn set(&mut self, x: u32, y: u32) {
-        let index = x + y * self.size.0;
-        self.data.set(index as usize, true);
-    }
-}
@@ -60,6 +60,25 @@ impl RbxClient {
         .await
     }
 
+    /// How many more badges the universe can create for free today.
+    pub async fn get_free_badges_quota(&self) -> Result<u64> {
+        let api_key = self.api_key_header()?.to_string();
+        let url = format!(
+            "https://apis.roblox.com/legacy-badges/v1/universes/{}/free-badges-quota",
+            self.universe_id
+        );
+
+        self.execute_json(|| async {
+            Ok(self
+                .client
+                .get(&url)
+                .header("x-api-key", &api_key)
+                .send()
+                .await?)
+        })
+        .await
+    }
+
     pub async fn create_badge(
         &self,
         name: &str,
@@ -82,7 +101,7 @@ impl RbxClient {
             .text("isActive", "true".to_string());
 
         if let Some(path) = icon_path {
-            let bytes = crate::icon::process_icon(path, self.bleed)?;
+            let bytes = self.icon_bytes(path)?;
             let part = multipart::Part::bytes(bytes)
                 .file_name("icon.png")
                 .mime_str("image/png")?;
@@ -153,7 +172,7 @@ impl RbxClient {
             badge_id
         );
 
-        let bytes = crate::icon::process_icon(icon_path, self.bleed)?;
+        let bytes = self.icon_bytes(icon_path)?;
         let part = multipart::Part::bytes(bytes)
             .file_name("icon.png")
             .mime_str("image/png")?;
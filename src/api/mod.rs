@@ -3,6 +3,8 @@ pub mod models;
 pub mod passes;
 pub mod products;
 
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::{bail, Result};
@@ -15,6 +17,8 @@ pub struct RbxClient {
     pub api_key: Option<String>,
     pub universe_id: u64,
     pub bleed: bool,
+    /// Processed icon bytes, filled ahead of time by `preload_icons`.
+    pub icon_cache: HashMap<PathBuf, Vec<u8>>,
 }
 
 impl RbxClient {
@@ -24,6 +28,22 @@ impl RbxClient {
             api_key,
             universe_id,
             bleed,
+            icon_cache: HashMap::new(),
+        }
+    }
+
+    /// Process icons in parallel ahead of uploading so the API calls reuse the results.
+    pub fn preload_icons(&mut self, paths: Vec<PathBuf>) -> Result<()> {
+        let processed = crate::icon::process_icons(&paths, self.bleed)?;
+        self.icon_cache.extend(paths.into_iter().zip(processed));
+        Ok(())
+    }
+
+    /// Processed PNG bytes for an icon, taken from the preload cache if available.
+    pub fn icon_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        match self.icon_cache.get(path) {
+            Some(bytes) => Ok(bytes.clone()),
+            None => crate::icon::process_icon(path, self.bleed),
         }
     }
 
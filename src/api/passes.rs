@@ -72,7 +72,7 @@ impl RbxClient {
             form = form.text("price", p.to_string());
         }
         if let Some(path) = icon_path {
-            let bytes = crate::icon::process_icon(path, self.bleed)?;
+            let bytes = self.icon_bytes(path)?;
             let part = multipart::Part::bytes(bytes)
                 .file_name("icon.png")
                 .mime_str("image/png")?;
@@ -126,7 +126,7 @@ impl RbxClient {
             form = form.text("price", p.to_string());
         }
         if let Some(path) = icon_path {
-            let bytes = crate::icon::process_icon(path, self.bleed)?;
+            let bytes = self.icon_bytes(path)?;
             let part = multipart::Part::bytes(bytes)
                 .file_name("icon.png")
                 .mime_str("image/png")?;
@@ -70,7 +70,7 @@ impl RbxClient {
             .text("price", price.to_string());
 
         if let Some(path) = icon_path {
-            let bytes = crate::icon::process_icon(path, self.bleed)?;
+            let bytes = self.icon_bytes(path)?;
             let part = multipart::Part::bytes(bytes)
                 .file_name("icon.png")
                 .mime_str("image/png")?;
@@ -157,7 +157,7 @@ impl RbxClient {
             .text("price", price.to_string());
 
         if let Some(path) = icon_path {
-            let bytes = crate::icon::process_icon(path, self.bleed)?;
+            let bytes = self.icon_bytes(path)?;
             let part = multipart::Part::bytes(bytes)
                 .file_name("icon.png")
                 .mime_str("image/png")?;
@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 
+use anyhow::{bail, Result};
 use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
@@ -43,6 +44,38 @@ Required API scopes:
     https://create.roblox.com/docs/cloud/features/assets#/"
     )]
     pub api_key: Option<String>,
+
+    /// Only act on resources matching this expression (repeatable)
+    #[arg(
+        long,
+        global = true,
+        value_name = "EXPR",
+        long_help = "\
+Only act on resources matching this expression. Repeat to select
+resources matching any of the expressions.
+
+Terms are joined with ',' (all must match) or '|' (any may match):
+
+  tag:<name>      resources tagged <name>
+  type:<section>  passes, badges, products or extra
+  key:<glob>      keys matching the glob ('*' and '?')
+  <glob>          shorthand for key:<glob>
+  !<term>         negates a term
+
+Example: --select 'tag:event:halloween,!type:badges'"
+    )]
+    pub select: Vec<String>,
+}
+
+impl Cli {
+    /// Fail if `--select` was passed to `command`, which always acts on
+    /// every resource.
+    pub fn reject_select(&self, command: &str) -> Result<()> {
+        if !self.select.is_empty() {
+            bail!("--select isn't supported by `rbxsync {command}`");
+        }
+        Ok(())
+    }
 }
 
 #[derive(Subcommand)]
@@ -60,6 +93,9 @@ pub enum Commands {
 
     /// Sync local config to Roblox
     Sync {
+        /// Only sync these resources, as <type>.<key> (globs allowed), e.g. products.Coins100
+        selectors: Vec<String>,
+
         /// Show what would change without applying
         #[arg(long)]
         dry_run: bool,
@@ -68,9 +104,21 @@ pub enum Commands {
         #[arg(long, value_delimiter = ',')]
         only: Option<Vec<ResourceType>>,
 
-        /// Expected cost in Robux when creating a badge (default: 0)
-        #[arg(long, default_value_t = 0)]
-        badge_cost: u64,
+        /// Apply product schedules as of this time instead of now (e.g. 2026-10-31T12:00:00Z)
+        #[arg(long, value_name = "TIME")]
+        at: Option<String>,
+
+        /// Apply price changes that break the [guardrails] thresholds
+        #[arg(long)]
+        allow_price_change: bool,
+
+        /// Expected cost in Robux per badge created (default: discovered from the free badge quota)
+        #[arg(long)]
+        badge_cost: Option<u64>,
+
+        /// Apply changes without asking for confirmation (the default when not attached to a terminal)
+        #[arg(long, short = 'y')]
+        yes: bool,
     },
 
     /// List remote resources (passes, badges, products)
@@ -84,6 +132,9 @@ pub enum Commands {
 
     /// Pull remote state into lockfile
     Pull {
+        /// Only refresh these resources, as <type>.<key> (globs allowed), e.g. passes.VIP
+        selectors: Vec<String>,
+
         /// Show what remote state differs without writing anything
         #[arg(long)]
         dry_run: bool,
@@ -97,6 +148,13 @@ pub enum Commands {
         accept_local: bool,
     },
 
+    /// Regenerate codegen outputs from the lockfile (no API key needed)
+    Codegen {
+        /// Exit with an error if any generated file differs instead of writing
+        #[arg(long)]
+        check: bool,
+    },
+
     /// Rename a resource key in config and lockfile
     Rename {
         /// Resource type
@@ -106,6 +164,48 @@ pub enum Commands {
         /// New key name
         new_key: String,
     },
+
+    /// Inspect and manage local icon files
+    Icons {
+        #[command(subcommand)]
+        command: IconsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum IconsCommand {
+    /// Render every icon with its Roblox crop into a PNG contact sheet
+    Preview {
+        /// Path to write the contact sheet to
+        #[arg(long, default_value = "icons-preview.png")]
+        output: PathBuf,
+
+        /// Number of icons per row
+        #[arg(long, default_value_t = 6)]
+        columns: u32,
+    },
+
+    /// Generate an HTML gallery of every resource's icon for review
+    Report {
+        /// Directory to write the report to
+        #[arg(long, default_value = "icons-report")]
+        output: PathBuf,
+    },
+
+    /// Find image files in the icon directory that no config entry references
+    Gc {
+        /// Show what would be removed without touching any files
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Delete orphaned icons
+        #[arg(long, conflicts_with = "move_to")]
+        delete: bool,
+
+        /// Move orphaned icons into this directory instead of deleting them (relative to the config file)
+        #[arg(long, conflicts_with = "delete")]
+        move_to: Option<PathBuf>,
+    },
 }
 
 #[derive(Clone, Debug, ValueEnum, PartialEq, Eq)]
@@ -1,10 +1,27 @@
-use std::collections::BTreeMap;
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 
-use crate::config::{CodegenStyle, Config};
-use crate::lockfile::Lockfile;
+use crate::config::{
+    CodegenConfig, CodegenFilter, CodegenLanguage, CodegenOutput, CodegenPaths, CodegenStyle,
+    Config, KeyCase, ReverseLookup,
+};
+use crate::lockfile::{BadgeLock, Lockfile, PassLock, ProductLock};
+use crate::select::{Section, Selection};
+
+pub mod manifest;
+mod marketplace;
+pub mod model;
+pub mod rojo;
+mod template;
+
+pub use manifest::{generate_manifest, render_manifest};
+pub use marketplace::{generate_marketplace, render_marketplace};
+pub use model::{generate_model, render_model};
+pub use rojo::{resolve_output, rojo_warnings};
+use template::render_template_file;
+pub use template::{generate_template, render_template};
 
 const LUAU_RESERVED: &[&str] = &[
     "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "if", "in", "local",
@@ -88,46 +105,319 @@ fn format_ts_key(key: &str) -> String {
     }
 }
 
+/// A `const enum` member name for `key`. TypeScript rejects numeric member
+/// names, so keys that start with a digit or parse as a number get a `_` prefix.
+fn ts_enum_member(key: &str) -> String {
+    let numeric = key.starts_with(|c: char| c.is_ascii_digit()) || key.parse::<f64>().is_ok();
+    if numeric {
+        format_ts_key(&format!("_{key}"))
+    } else {
+        format_ts_key(key)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tree types
 // ---------------------------------------------------------------------------
 
 pub type CodegenTree = BTreeMap<String, CodegenNode>;
 
+#[derive(Debug)]
 pub enum CodegenNode {
-    Leaf(u64),
+    Leaf(CodegenLeaf),
     Branch(BTreeMap<String, CodegenNode>),
 }
 
+/// A single generated entry. Synced resources carry their lockfile metadata;
+/// entries from `codegen.extra` only have an ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodegenLeaf {
+    pub id: u64,
+    pub source: LeafSource,
+    /// The entry's own name in its table, after `codegen.key_case`
+    pub key: String,
+    pub name: Option<String>,
+    pub price: Option<u64>,
+    pub description: Option<String>,
+    pub icon_asset_id: Option<u64>,
+    pub for_sale: Option<bool>,
+    /// The resource's `data` table from the config
+    pub data: BTreeMap<String, toml::Value>,
+}
+
+/// Where a leaf came from: a synced resource (by config key) or a `codegen.extra` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeafSource {
+    Pass(String),
+    Badge(String),
+    Product(String),
+    Extra(String),
+}
+
+impl LeafSource {
+    /// The key the entry is known by: the config key for resources, or the
+    /// last segment of the dotted key for extra entries.
+    pub fn key(&self) -> &str {
+        match self {
+            LeafSource::Pass(key) | LeafSource::Badge(key) | LeafSource::Product(key) => key,
+            LeafSource::Extra(full_key) => full_key.rsplit('.').next().unwrap_or(full_key),
+        }
+    }
+}
+
+impl std::fmt::Display for LeafSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeafSource::Pass(key) => write!(f, "pass '{key}'"),
+            LeafSource::Badge(key) => write!(f, "badge '{key}'"),
+            LeafSource::Product(key) => write!(f, "product '{key}'"),
+            LeafSource::Extra(key) => write!(f, "codegen.extra '{key}'"),
+        }
+    }
+}
+
+impl CodegenLeaf {
+    pub fn extra(full_key: &str, id: u64) -> Self {
+        Self {
+            id,
+            source: LeafSource::Extra(full_key.to_string()),
+            key: full_key.rsplit('.').next().unwrap_or(full_key).to_string(),
+            name: None,
+            price: None,
+            description: None,
+            icon_asset_id: None,
+            for_sale: None,
+            data: BTreeMap::new(),
+        }
+    }
+
+    pub fn pass(key: &str, lock: &PassLock) -> Self {
+        Self {
+            id: lock.id,
+            source: LeafSource::Pass(key.to_string()),
+            key: key.to_string(),
+            name: Some(lock.name.clone()),
+            price: lock.price,
+            description: lock.description.clone(),
+            icon_asset_id: lock.icon_asset_id,
+            for_sale: Some(lock.for_sale),
+            data: BTreeMap::new(),
+        }
+    }
+
+    pub fn badge(key: &str, lock: &BadgeLock) -> Self {
+        Self {
+            id: lock.id,
+            source: LeafSource::Badge(key.to_string()),
+            key: key.to_string(),
+            name: Some(lock.name.clone()),
+            price: None,
+            description: lock.description.clone(),
+            icon_asset_id: lock.icon_asset_id,
+            for_sale: None,
+            data: BTreeMap::new(),
+        }
+    }
+
+    pub fn product(key: &str, lock: &ProductLock) -> Self {
+        Self {
+            id: lock.id,
+            source: LeafSource::Product(key.to_string()),
+            key: key.to_string(),
+            name: Some(lock.name.clone()),
+            price: Some(lock.price),
+            description: lock.description.clone(),
+            icon_asset_id: lock.icon_asset_id,
+            for_sale: Some(lock.for_sale),
+            data: BTreeMap::new(),
+        }
+    }
+
+    fn with_data(mut self, data: Option<&BTreeMap<String, toml::Value>>) -> Self {
+        self.data = data.cloned().unwrap_or_default();
+        self
+    }
+
+    fn with_key_case(mut self, case: KeyCase) -> Self {
+        self.key = transform_key(&self.key, case);
+        self
+    }
+}
+
+/// Options controlling how a `CodegenTree` is rendered.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Emit each leaf as a record table instead of a bare ID.
+    pub records: bool,
+    /// Emit a `byId` reverse lookup table at the root.
+    pub reverse: Option<ReverseLookup>,
+    /// Emit `--!strict`, exported types and frozen tables in Luau output.
+    pub strict: bool,
+}
+
+impl From<&CodegenConfig> for RenderOptions {
+    fn from(codegen: &CodegenConfig) -> Self {
+        Self {
+            records: codegen.records,
+            reverse: codegen.reverse,
+            strict: codegen.strict,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tree building
 // ---------------------------------------------------------------------------
 
-/// Insert a leaf (`key = id`) into `tree` at the location described by `segments`.
-///
-/// Each segment becomes a `Branch`; the final `key` becomes a `Leaf`.
-fn insert_into_tree(tree: &mut CodegenTree, segments: &[&str], key: &str, id: u64) {
-    if segments.is_empty() {
-        tree.insert(key.to_string(), CodegenNode::Leaf(id));
-        return;
+/// Split a key into words at `_`, `-`, spaces and case changes
+/// (`vipPass`, `VIPPass` and `vip_pass` all give `vip`, `pass`).
+fn split_words(key: &str) -> Vec<String> {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c == ' ' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
     }
+    words
+}
 
-    let node = tree
-        .entry(segments[0].to_string())
-        .or_insert_with(|| CodegenNode::Branch(BTreeMap::new()));
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/// Apply `codegen.key_case` to a resource key.
+pub fn transform_key(key: &str, case: KeyCase) -> String {
+    let words = split_words(key);
+    match case {
+        KeyCase::Preserve => key.to_string(),
+        KeyCase::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                if i == 0 {
+                    w.to_lowercase()
+                } else {
+                    capitalize(w)
+                }
+            })
+            .collect(),
+        KeyCase::Pascal => words.iter().map(|w| capitalize(w)).collect(),
+        KeyCase::Snake => words
+            .iter()
+            .map(|w| w.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        KeyCase::ScreamingSnake => words
+            .iter()
+            .map(|w| w.to_uppercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+    }
+}
 
+/// Describe what a node was generated from, for collision errors.
+fn node_source(node: &CodegenNode) -> String {
     match node {
-        CodegenNode::Branch(children) => {
-            insert_into_tree(children, &segments[1..], key, id);
-        }
-        CodegenNode::Leaf(_) => {
-            // A leaf already exists at this segment — promote it to a branch.
-            // This shouldn't happen with well-formed configs, but handle gracefully.
-            let mut children = BTreeMap::new();
-            insert_into_tree(&mut children, &segments[1..], key, id);
-            *node = CodegenNode::Branch(children);
-        }
+        CodegenNode::Leaf(leaf) => leaf.source.to_string(),
+        CodegenNode::Branch(children) => match leaves(children).first() {
+            Some((_, leaf)) => leaf.source.to_string(),
+            None => "another entry".to_string(),
+        },
+    }
+}
+
+/// Insert `leaf` into `table` under `key`, where `path` is the dotted path of
+/// the new entry. Fails if anything is already there: another entry with the
+/// same path, or a table that other entries are nested under.
+fn insert_leaf(
+    table: &mut BTreeMap<String, CodegenNode>,
+    path: &str,
+    key: &str,
+    leaf: CodegenLeaf,
+) -> Result<()> {
+    match table.get(key) {
+        Some(CodegenNode::Leaf(existing)) => bail!(
+            "Codegen path collision: {} and {} are both generated at '{}'",
+            existing.source,
+            leaf.source,
+            path
+        ),
+        Some(branch @ CodegenNode::Branch(_)) => bail!(
+            "Codegen path collision: {} is generated at '{}', but {} needs '{}' to be a table",
+            leaf.source,
+            path,
+            node_source(branch),
+            path
+        ),
+        None => {}
+    }
+    table.insert(key.to_string(), CodegenNode::Leaf(leaf));
+    Ok(())
+}
+
+/// Insert a leaf (`key = id`) into `tree` at the location described by `segments`.
+///
+/// Each segment becomes a `Branch`; the final `key` becomes a `Leaf`. Fails
+/// instead of overwriting if the path is already taken.
+fn insert_into_tree(
+    tree: &mut CodegenTree,
+    segments: &[&str],
+    key: &str,
+    leaf: CodegenLeaf,
+) -> Result<()> {
+    let mut table = tree;
+    for (i, segment) in segments.iter().enumerate() {
+        let node = table
+            .entry(segment.to_string())
+            .or_insert_with(|| CodegenNode::Branch(BTreeMap::new()));
+        table = match node {
+            CodegenNode::Branch(children) => children,
+            CodegenNode::Leaf(existing) => {
+                let branch_path = segments[..=i].join(".");
+                bail!(
+                    "Codegen path collision: {} is generated at '{}', but {} needs '{}' to be a table",
+                    existing.source,
+                    branch_path,
+                    leaf.source,
+                    branch_path
+                );
+            }
+        };
     }
+
+    let path = if segments.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{key}", segments.join("."))
+    };
+    insert_leaf(table, &path, key, leaf)
 }
 
 /// Resolve the effective path string for an item.
@@ -136,70 +426,235 @@ fn resolve_path<'a>(item_path: Option<&'a str>, section_default: &'a str) -> &'a
 }
 
 /// Build a `CodegenTree` from a lockfile + config, resolving per-item and
-/// per-section custom paths. Respects `config.codegen.style`.
-pub fn build_tree(lockfile: &Lockfile, config: &Config) -> CodegenTree {
+/// per-section custom paths. Respects `config.codegen.style` and
+/// `config.codegen.key_case`.
+pub fn build_tree(lockfile: &Lockfile, config: &Config) -> Result<CodegenTree> {
+    let codegen = &config.codegen;
+    let layout = TreeLayout {
+        style: &codegen.style,
+        paths: &codegen.paths,
+        key_case: codegen.key_case,
+    };
+    build_tree_with(lockfile, config, &layout, None)
+}
+
+/// Build the tree for one `[[codegen.outputs]]` entry: its own style and
+/// section paths (falling back to `[codegen]`), keeping only the entries its
+/// filters select.
+pub fn build_output_tree(
+    lockfile: &Lockfile,
+    config: &Config,
+    output: &CodegenOutput,
+) -> Result<CodegenTree> {
+    let codegen = &config.codegen;
+    let paths = CodegenPaths {
+        passes: output.paths.passes.clone().or(codegen.paths.passes.clone()),
+        badges: output.paths.badges.clone().or(codegen.paths.badges.clone()),
+        products: output
+            .paths
+            .products
+            .clone()
+            .or(codegen.paths.products.clone()),
+    };
+    let layout = TreeLayout {
+        style: output.style.as_ref().unwrap_or(&codegen.style),
+        paths: &paths,
+        key_case: output.key_case.unwrap_or(codegen.key_case),
+    };
+    build_tree_with(lockfile, config, &layout, Some(output))
+}
+
+/// Path collisions in the trees `generate` builds: the main tree and one
+/// per `[[codegen.outputs]]` entry. Resources in the config that haven't been
+/// synced yet are included with a placeholder ID, so collisions show up
+/// before anything is created.
+pub fn path_collisions(lockfile: &Lockfile, config: &Config) -> Vec<anyhow::Error> {
+    let mut planned = Lockfile {
+        version: lockfile.version,
+        universe_id: lockfile.universe_id,
+        passes: lockfile.passes.clone(),
+        badges: lockfile.badges.clone(),
+        products: lockfile.products.clone(),
+    };
+    for key in config.passes.keys() {
+        planned
+            .passes
+            .entry(key.clone())
+            .or_insert_with(|| PassLock {
+                id: 0,
+                name: key.clone(),
+                price: None,
+                description: None,
+                icon_asset_id: None,
+                icon_hash: None,
+                for_sale: true,
+                regional_pricing: false,
+            });
+    }
+    for key in config.badges.keys() {
+        planned
+            .badges
+            .entry(key.clone())
+            .or_insert_with(|| BadgeLock {
+                id: 0,
+                name: key.clone(),
+                description: None,
+                enabled: true,
+                icon_asset_id: None,
+                icon_hash: None,
+            });
+    }
+    for key in config.products.keys() {
+        planned
+            .products
+            .entry(key.clone())
+            .or_insert_with(|| ProductLock {
+                id: 0,
+                name: key.clone(),
+                price: 0,
+                description: None,
+                icon_asset_id: None,
+                icon_hash: None,
+                for_sale: true,
+                regional_pricing: false,
+                store_page: false,
+            });
+    }
+
+    let mut errors: Vec<anyhow::Error> = build_tree(&planned, config).err().into_iter().collect();
+    for output in &config.codegen.outputs {
+        if let Err(err) = build_output_tree(&planned, config, output) {
+            errors.push(err.context(format!("In codegen output {}", output.path.display())));
+        }
+    }
+    errors
+}
+
+/// Settings that decide where, and under which name, entries land in a tree.
+struct TreeLayout<'a> {
+    style: &'a CodegenStyle,
+    paths: &'a CodegenPaths,
+    key_case: KeyCase,
+}
+
+/// Whether `output`'s include/exclude filters and `select` expression select
+/// an entry.
+fn output_selects(output: &CodegenOutput, source: &LeafSource, tags: &[String]) -> bool {
+    // Extra entries are selected by their full dotted key.
+    let (section, key) = match source {
+        LeafSource::Pass(key) => (Section::Passes, key),
+        LeafSource::Badge(key) => (Section::Badges, key),
+        LeafSource::Product(key) => (Section::Products, key),
+        LeafSource::Extra(full_key) => (Section::Extra, full_key),
+    };
+    if output
+        .select
+        .as_ref()
+        .is_some_and(|select| !select.matches(section, key, tags))
+    {
+        return false;
+    }
+
+    let matches = |filter: &CodegenFilter| match filter {
+        CodegenFilter::Passes => matches!(source, LeafSource::Pass(_)),
+        CodegenFilter::Badges => matches!(source, LeafSource::Badge(_)),
+        CodegenFilter::Products => matches!(source, LeafSource::Product(_)),
+        CodegenFilter::Extra => matches!(source, LeafSource::Extra(_)),
+        CodegenFilter::Tag(tag) => tags.contains(tag),
+    };
+    (output.include.is_empty() || output.include.iter().any(matches))
+        && !output.exclude.iter().any(matches)
+}
+
+fn build_tree_with(
+    lockfile: &Lockfile,
+    config: &Config,
+    layout: &TreeLayout,
+    output: Option<&CodegenOutput>,
+) -> Result<CodegenTree> {
     let mut tree = CodegenTree::new();
-    let flat = config.codegen.style == CodegenStyle::Flat;
+    let flat = *layout.style == CodegenStyle::Flat;
+    let paths = layout.paths;
+    let selects = |source: &LeafSource, tags: Option<&Vec<String>>| {
+        output
+            .is_none_or(|output| output_selects(output, source, tags.map_or(&[], |t| t.as_slice())))
+    };
 
-    let default_pass_path = config.codegen.paths.passes.as_deref().unwrap_or("passes");
-    let default_badge_path = config.codegen.paths.badges.as_deref().unwrap_or("badges");
-    let default_product_path = config
-        .codegen
-        .paths
-        .products
-        .as_deref()
-        .unwrap_or("products");
+    let default_pass_path = paths.passes.as_deref().unwrap_or("passes");
+    let default_badge_path = paths.badges.as_deref().unwrap_or("badges");
+    let default_product_path = paths.products.as_deref().unwrap_or("products");
 
     for (key, lock) in &lockfile.passes {
-        let path_str = resolve_path(
-            config.passes.get(key).and_then(|c| c.path.as_deref()),
-            default_pass_path,
-        );
-        insert_item(&mut tree, path_str, key, lock.id, flat);
+        let item = config.passes.get(key);
+        let leaf = CodegenLeaf::pass(key, lock)
+            .with_data(item.map(|c| &c.data))
+            .with_key_case(layout.key_case);
+        if !selects(&leaf.source, item.map(|c| &c.tags)) {
+            continue;
+        }
+        let path_str = resolve_path(item.and_then(|c| c.path.as_deref()), default_pass_path);
+        let leaf_key = leaf.key.clone();
+        insert_item(&mut tree, path_str, &leaf_key, leaf, flat)?;
     }
 
     for (key, lock) in &lockfile.badges {
-        let path_str = resolve_path(
-            config.badges.get(key).and_then(|c| c.path.as_deref()),
-            default_badge_path,
-        );
-        insert_item(&mut tree, path_str, key, lock.id, flat);
+        let item = config.badges.get(key);
+        let leaf = CodegenLeaf::badge(key, lock)
+            .with_data(item.map(|c| &c.data))
+            .with_key_case(layout.key_case);
+        if !selects(&leaf.source, item.map(|c| &c.tags)) {
+            continue;
+        }
+        let path_str = resolve_path(item.and_then(|c| c.path.as_deref()), default_badge_path);
+        let leaf_key = leaf.key.clone();
+        insert_item(&mut tree, path_str, &leaf_key, leaf, flat)?;
     }
 
     for (key, lock) in &lockfile.products {
-        let path_str = resolve_path(
-            config.products.get(key).and_then(|c| c.path.as_deref()),
-            default_product_path,
-        );
-        insert_item(&mut tree, path_str, key, lock.id, flat);
+        let item = config.products.get(key);
+        let leaf = CodegenLeaf::product(key, lock)
+            .with_data(item.map(|c| &c.data))
+            .with_key_case(layout.key_case);
+        if !selects(&leaf.source, item.map(|c| &c.tags)) {
+            continue;
+        }
+        let path_str = resolve_path(item.and_then(|c| c.path.as_deref()), default_product_path);
+        let leaf_key = leaf.key.clone();
+        insert_item(&mut tree, path_str, &leaf_key, leaf, flat)?;
     }
 
     // Extra entries: "dotted.path.key" = id
     for (full_key, &id) in &config.codegen.extra {
-        if flat {
-            tree.insert(full_key.clone(), CodegenNode::Leaf(id));
-        } else if let Some(dot_pos) = full_key.rfind('.') {
-            let path_str = &full_key[..dot_pos];
-            let leaf_key = &full_key[dot_pos + 1..];
-            let segments: Vec<&str> = path_str.split('.').collect();
-            insert_into_tree(&mut tree, &segments, leaf_key, id);
+        let leaf = CodegenLeaf::extra(full_key, id).with_key_case(layout.key_case);
+        if !selects(&leaf.source, None) {
+            continue;
+        }
+        let leaf_key = leaf.key.clone();
+        if let Some(dot_pos) = full_key.rfind('.') {
+            insert_item(&mut tree, &full_key[..dot_pos], &leaf_key, leaf, flat)?;
         } else {
             // No dot — insert directly at root
-            tree.insert(full_key.clone(), CodegenNode::Leaf(id));
+            insert_leaf(&mut tree, &leaf_key, &leaf_key, leaf)?;
         }
     }
 
-    tree
+    Ok(tree)
 }
 
 /// Insert an item into the tree, using flat or nested style.
-fn insert_item(tree: &mut CodegenTree, path_str: &str, key: &str, id: u64, flat: bool) {
+fn insert_item(
+    tree: &mut CodegenTree,
+    path_str: &str,
+    key: &str,
+    leaf: CodegenLeaf,
+    flat: bool,
+) -> Result<()> {
     if flat {
         let flat_key = format!("{path_str}.{key}");
-        tree.insert(flat_key, CodegenNode::Leaf(id));
+        insert_leaf(tree, &flat_key, &flat_key, leaf)
     } else {
         let segments: Vec<&str> = path_str.split('.').collect();
-        insert_into_tree(tree, &segments, key, id);
+        insert_into_tree(tree, &segments, key, leaf)
     }
 }
 
@@ -210,13 +665,21 @@ pub fn build_tree_default(lockfile: &Lockfile) -> CodegenTree {
     let mut tree = CodegenTree::new();
 
     for (key, lock) in &lockfile.passes {
-        insert_into_tree(&mut tree, &["passes"], key, lock.id);
+        insert_into_tree(&mut tree, &["passes"], key, CodegenLeaf::pass(key, lock))
+            .expect("lockfile keys are unique");
     }
     for (key, lock) in &lockfile.badges {
-        insert_into_tree(&mut tree, &["badges"], key, lock.id);
+        insert_into_tree(&mut tree, &["badges"], key, CodegenLeaf::badge(key, lock))
+            .expect("lockfile keys are unique");
     }
     for (key, lock) in &lockfile.products {
-        insert_into_tree(&mut tree, &["products"], key, lock.id);
+        insert_into_tree(
+            &mut tree,
+            &["products"],
+            key,
+            CodegenLeaf::product(key, lock),
+        )
+        .expect("lockfile keys are unique");
     }
 
     tree
@@ -228,74 +691,499 @@ pub fn build_tree_default_flat(lockfile: &Lockfile) -> CodegenTree {
     let mut tree = CodegenTree::new();
 
     for (key, lock) in &lockfile.passes {
-        tree.insert(format!("passes.{key}"), CodegenNode::Leaf(lock.id));
+        tree.insert(
+            format!("passes.{key}"),
+            CodegenNode::Leaf(CodegenLeaf::pass(key, lock)),
+        );
     }
     for (key, lock) in &lockfile.badges {
-        tree.insert(format!("badges.{key}"), CodegenNode::Leaf(lock.id));
+        tree.insert(
+            format!("badges.{key}"),
+            CodegenNode::Leaf(CodegenLeaf::badge(key, lock)),
+        );
     }
     for (key, lock) in &lockfile.products {
-        tree.insert(format!("products.{key}"), CodegenNode::Leaf(lock.id));
+        tree.insert(
+            format!("products.{key}"),
+            CodegenNode::Leaf(CodegenLeaf::product(key, lock)),
+        );
     }
 
     tree
 }
 
+// ---------------------------------------------------------------------------
+// Tree queries
+// ---------------------------------------------------------------------------
+
+/// Name of the root field holding the reverse lookup table.
+pub const REVERSE_FIELD: &str = "byId";
+
+/// Every leaf in `tree` together with its dotted path, in table order.
+pub fn leaves(tree: &CodegenTree) -> Vec<(String, &CodegenLeaf)> {
+    fn walk<'a>(
+        nodes: &'a BTreeMap<String, CodegenNode>,
+        prefix: &str,
+        out: &mut Vec<(String, &'a CodegenLeaf)>,
+    ) {
+        for (key, node) in nodes {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            match node {
+                CodegenNode::Leaf(leaf) => out.push((path, leaf)),
+                CodegenNode::Branch(children) => walk(children, &path, out),
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(tree, "", &mut out);
+    out
+}
+
+/// Build the `id -> key` or `id -> path` reverse lookup table.
+///
+/// Fails if two entries share an ID, which usually means a `codegen.extra`
+/// entry duplicates a synced resource or another extra entry.
+pub fn reverse_lookup(tree: &CodegenTree, mode: ReverseLookup) -> Result<BTreeMap<u64, String>> {
+    if tree.contains_key(REVERSE_FIELD) {
+        bail!(
+            "Cannot generate the reverse lookup table: '{}' is already used as a key",
+            REVERSE_FIELD
+        );
+    }
+
+    let mut seen: BTreeMap<u64, (String, &CodegenLeaf)> = BTreeMap::new();
+    for (path, leaf) in leaves(tree) {
+        if let Some((other_path, other)) = seen.get(&leaf.id) {
+            bail!(
+                "Duplicate ID {} in generated code: {} ('{}') and {} ('{}')",
+                leaf.id,
+                other.source,
+                other_path,
+                leaf.source,
+                path
+            );
+        }
+        seen.insert(leaf.id, (path, leaf));
+    }
+
+    Ok(seen
+        .into_iter()
+        .map(|(id, (path, leaf))| {
+            let value = match mode {
+                ReverseLookup::Key => leaf.source.key().to_string(),
+                ReverseLookup::Path => path,
+            };
+            (id, value)
+        })
+        .collect())
+}
+
 // ---------------------------------------------------------------------------
 // Luau rendering
 // ---------------------------------------------------------------------------
 
-fn render_luau_node(out: &mut String, node: &CodegenNode, depth: usize) {
+/// Quote and escape a string as a Luau (or TypeScript) string literal.
+fn string_literal(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn icon_uri(asset_id: u64) -> String {
+    format!("rbxassetid://{asset_id}")
+}
+
+const LUAU_RECORD_TYPE: &str = "\
+export type AssetRecord = {
+\tid: number,
+\tname: string?,
+\tprice: number?,
+\tdescription: string?,
+\ticonId: string?,
+\tforSale: boolean?,
+}
+";
+
+/// Opening and closing of a table constructor; strict output freezes every table.
+fn luau_table_delimiters(options: &RenderOptions) -> (&'static str, &'static str) {
+    if options.strict {
+        ("table.freeze({", "})")
+    } else {
+        ("{", "}")
+    }
+}
+
+/// A `data` value as a Luau expression, on one line.
+fn luau_value(value: &toml::Value, options: &RenderOptions) -> String {
+    match value {
+        toml::Value::String(s) => string_literal(s),
+        toml::Value::Integer(n) => n.to_string(),
+        toml::Value::Float(f) if f.is_nan() => "0 / 0".to_string(),
+        toml::Value::Float(f) if f.is_infinite() => {
+            if *f > 0.0 { "math.huge" } else { "-math.huge" }.to_string()
+        }
+        toml::Value::Float(f) => format!("{f:?}"),
+        toml::Value::Boolean(b) => b.to_string(),
+        toml::Value::Datetime(datetime) => string_literal(&datetime.to_string()),
+        toml::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(|v| luau_value(v, options)).collect();
+            luau_inline_table(&items, options)
+        }
+        toml::Value::Table(table) => luau_data_table(table.iter(), options),
+    }
+}
+
+fn luau_data_table<'a>(
+    fields: impl Iterator<Item = (&'a String, &'a toml::Value)>,
+    options: &RenderOptions,
+) -> String {
+    let fields: Vec<String> = fields
+        .map(|(key, value)| format!("{} = {}", format_key(key), luau_value(value, options)))
+        .collect();
+    luau_inline_table(&fields, options)
+}
+
+fn luau_inline_table(items: &[String], options: &RenderOptions) -> String {
+    let (open, close) = luau_table_delimiters(options);
+    if items.is_empty() {
+        format!("{open}{close}")
+    } else {
+        format!("{open} {} {close}", items.join(", "))
+    }
+}
+
+/// The Luau type inferred from a `data` value.
+fn luau_value_type(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(_) | toml::Value::Datetime(_) => "string".to_string(),
+        toml::Value::Integer(_) | toml::Value::Float(_) => "number".to_string(),
+        toml::Value::Boolean(_) => "boolean".to_string(),
+        toml::Value::Array(items) => {
+            let types: BTreeSet<String> = items.iter().map(luau_value_type).collect();
+            if types.is_empty() {
+                "{ any }".to_string()
+            } else {
+                format!(
+                    "{{ {} }}",
+                    types.into_iter().collect::<Vec<_>>().join(" | ")
+                )
+            }
+        }
+        toml::Value::Table(table) => luau_data_type(table.iter()),
+    }
+}
+
+fn luau_data_type<'a>(fields: impl Iterator<Item = (&'a String, &'a toml::Value)>) -> String {
+    let fields: Vec<String> = fields
+        .map(|(key, value)| format!("{}: {}", format_key(key), luau_value_type(value)))
+        .collect();
+    if fields.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", fields.join(", "))
+    }
+}
+
+fn render_luau_record(out: &mut String, leaf: &CodegenLeaf, depth: usize, options: &RenderOptions) {
+    let indent = "\t".repeat(depth);
+    let (open, close) = luau_table_delimiters(options);
+    out.push_str(&format!("{open}\n"));
+    out.push_str(&format!("{indent}\tid = {},\n", leaf.id));
+    if let Some(name) = &leaf.name {
+        out.push_str(&format!("{indent}\tname = {},\n", string_literal(name)));
+    }
+    if let Some(price) = leaf.price {
+        out.push_str(&format!("{indent}\tprice = {price},\n"));
+    }
+    if let Some(description) = &leaf.description {
+        out.push_str(&format!(
+            "{indent}\tdescription = {},\n",
+            string_literal(description)
+        ));
+    }
+    if let Some(icon) = leaf.icon_asset_id {
+        out.push_str(&format!(
+            "{indent}\ticonId = {},\n",
+            string_literal(&icon_uri(icon))
+        ));
+    }
+    if let Some(for_sale) = leaf.for_sale {
+        out.push_str(&format!("{indent}\tforSale = {for_sale},\n"));
+    }
+    if !leaf.data.is_empty() {
+        out.push_str(&format!(
+            "{indent}\tdata = {},\n",
+            luau_data_table(leaf.data.iter(), options)
+        ));
+    }
+    out.push_str(&format!("{indent}{close},\n"));
+}
+
+fn render_luau_node(out: &mut String, node: &CodegenNode, depth: usize, options: &RenderOptions) {
     let indent = "\t".repeat(depth);
     match node {
-        CodegenNode::Leaf(id) => {
-            out.push_str(&format!("{id},\n"));
+        CodegenNode::Leaf(leaf) if options.records => {
+            render_luau_record(out, leaf, depth, options);
+        }
+        CodegenNode::Leaf(leaf) => {
+            out.push_str(&format!("{},\n", leaf.id));
         }
         CodegenNode::Branch(children) => {
-            out.push_str("{\n");
+            let (open, close) = luau_table_delimiters(options);
+            out.push_str(&format!("{open}\n"));
             for (key, child) in children {
                 out.push_str(&format!("{indent}\t{} = ", format_key(key)));
-                render_luau_node(out, child, depth + 1);
+                render_luau_node(out, child, depth + 1, options);
             }
-            out.push_str(&format!("{indent}}},\n"));
+            out.push_str(&format!("{indent}{close},\n"));
         }
     }
 }
 
+/// Render the Luau type of a table whose fields are `children`.
+fn render_luau_type(
+    out: &mut String,
+    children: &BTreeMap<String, CodegenNode>,
+    depth: usize,
+    options: &RenderOptions,
+) {
+    let indent = "\t".repeat(depth);
+    out.push_str("{\n");
+    for (key, child) in children {
+        out.push_str(&format!("{indent}\t{}: ", format_key(key)));
+        match child {
+            CodegenNode::Leaf(leaf) if options.records && !leaf.data.is_empty() => {
+                out.push_str(&format!(
+                    "AssetRecord & {{ data: {} }}",
+                    luau_data_type(leaf.data.iter())
+                ))
+            }
+            CodegenNode::Leaf(_) if options.records => out.push_str("AssetRecord"),
+            CodegenNode::Leaf(_) => out.push_str("number"),
+            CodegenNode::Branch(grandchildren) => {
+                render_luau_type(out, grandchildren, depth + 1, options)
+            }
+        }
+        out.push_str(",\n");
+    }
+    if depth == 0 && options.reverse.is_some() {
+        out.push_str(&format!("\t{REVERSE_FIELD}: {{ [number]: string }},\n"));
+    }
+    out.push_str(&format!("{indent}}}"));
+}
+
+/// Keys (after `codegen.key_case`) and IDs of the passes, badges and products
+/// in `tree`, labelled `Pass`, `Badge` and `Product`. Extra entries are not
+/// included.
+fn resource_ids(tree: &CodegenTree) -> [(&'static str, BTreeMap<&str, u64>); 3] {
+    let mut ids = [
+        ("Pass", BTreeMap::new()),
+        ("Badge", BTreeMap::new()),
+        ("Product", BTreeMap::new()),
+    ];
+    for (_, leaf) in leaves(tree) {
+        let index = match &leaf.source {
+            LeafSource::Pass(_) => 0,
+            LeafSource::Badge(_) => 1,
+            LeafSource::Product(_) => 2,
+            LeafSource::Extra(_) => continue,
+        };
+        ids[index].1.insert(leaf.key.as_str(), leaf.id);
+    }
+    ids
+}
+
+/// A union of string literal types, or `never` if there are no keys.
+fn key_union<'a>(keys: impl IntoIterator<Item = &'a str>) -> String {
+    let literals: Vec<String> = keys.into_iter().map(string_literal).collect();
+    if literals.is_empty() {
+        "never".to_string()
+    } else {
+        literals.join(" | ")
+    }
+}
+
+/// Write `content` to `path`, creating parent directories as needed.
+fn write_output(path: &Path, content: &str) -> Result<()> {
+    write_bytes(path, content.as_bytes())
+}
+
+fn write_bytes(path: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
 pub fn generate_luau(tree: &CodegenTree, output_path: &Path) -> Result<()> {
+    generate_luau_with(tree, output_path, &RenderOptions::default())
+}
+
+pub fn generate_luau_with(
+    tree: &CodegenTree,
+    output_path: &Path,
+    options: &RenderOptions,
+) -> Result<()> {
     let var_name = output_path
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("Assets");
 
+    write_output(output_path, &render_luau(tree, var_name, options)?)
+}
+
+/// Render the Luau module for `tree`, storing the table in `var_name`.
+pub fn render_luau(tree: &CodegenTree, var_name: &str, options: &RenderOptions) -> Result<String> {
+    render_luau_module(tree, var_name, options, var_name)
+}
+
+/// Render the Luau module for `tree`, ending with `return <returns>`.
+fn render_luau_module(
+    tree: &CodegenTree,
+    var_name: &str,
+    options: &RenderOptions,
+    returns: &str,
+) -> Result<String> {
     let mut out = String::new();
+    if options.strict {
+        out.push_str("--!strict\n");
+    }
     out.push_str("-- This file is auto-generated by rbxsync. Do not edit manually.\n\n");
-    out.push_str(&format!("local {} = {{\n", var_name));
+    if options.strict {
+        for (label, ids) in resource_ids(tree) {
+            out.push_str(&format!(
+                "export type {label}Key = {}\n",
+                key_union(ids.keys().copied())
+            ));
+        }
+        out.push('\n');
+    }
+    if options.records {
+        out.push_str(LUAU_RECORD_TYPE);
+        out.push('\n');
+    }
+    let (open, close) = luau_table_delimiters(options);
+    // Records are annotated so consumers get AssetRecord checking without strict mode
+    if options.strict || options.records {
+        out.push_str(&format!("export type {var_name} = "));
+        render_luau_type(&mut out, tree, 0, options);
+        out.push_str("\n\n");
+        out.push_str(&format!("local {var_name}: {var_name} = {open}\n"));
+    } else {
+        out.push_str(&format!("local {} = {{\n", var_name));
+    }
 
     for (key, node) in tree {
         out.push_str(&format!("\t{} = ", format_key(key)));
-        render_luau_node(&mut out, node, 1);
+        render_luau_node(&mut out, node, 1, options);
     }
 
-    out.push_str("}\n\n");
-    out.push_str(&format!("return {}\n", var_name));
-
-    if let Some(parent) = output_path.parent() {
-        std::fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    if let Some(mode) = options.reverse {
+        out.push_str(&format!("\t{REVERSE_FIELD} = {open}\n"));
+        for (id, value) in reverse_lookup(tree, mode)? {
+            out.push_str(&format!("\t\t[{id}] = {},\n", string_literal(&value)));
+        }
+        out.push_str(&format!("\t{close},\n"));
     }
-    std::fs::write(output_path, out)
-        .with_context(|| format!("Failed to write {}", output_path.display()))?;
 
-    Ok(())
+    out.push_str(&format!("{close}\n\n"));
+    out.push_str(&format!("return {returns}\n"));
+
+    Ok(out)
 }
 
 // ---------------------------------------------------------------------------
 // TypeScript rendering
 // ---------------------------------------------------------------------------
 
-fn render_ts_node(out: &mut String, node: &CodegenNode, depth: usize) {
+/// The `AssetRecord` interface, with each line prefixed by `indent`.
+fn ts_record_type(indent: &str, export: &str) -> String {
+    let fields = [
+        "readonly id: number",
+        "readonly name?: string",
+        "readonly price?: number",
+        "readonly description?: string",
+        "readonly iconId?: string",
+        "readonly forSale?: boolean",
+    ];
+    let mut out = format!("{indent}{export}interface AssetRecord {{\n");
+    for field in fields {
+        out.push_str(&format!("{indent}\t{field}\n"));
+    }
+    out.push_str(&format!("{indent}}}\n"));
+    out
+}
+
+/// The TypeScript type inferred from a `data` value.
+fn ts_value_type(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(_) | toml::Value::Datetime(_) => "string".to_string(),
+        toml::Value::Integer(_) | toml::Value::Float(_) => "number".to_string(),
+        toml::Value::Boolean(_) => "boolean".to_string(),
+        toml::Value::Array(items) => {
+            let types: BTreeSet<String> = items.iter().map(ts_value_type).collect();
+            let element = match types.len() {
+                0 => "unknown".to_string(),
+                1 => types.into_iter().next().unwrap(),
+                _ => types.into_iter().collect::<Vec<_>>().join(" | "),
+            };
+            if element.contains(" | ") || element.starts_with("readonly ") {
+                format!("readonly ({element})[]")
+            } else {
+                format!("readonly {element}[]")
+            }
+        }
+        toml::Value::Table(table) => ts_data_type(table.iter()),
+    }
+}
+
+fn ts_data_type<'a>(fields: impl Iterator<Item = (&'a String, &'a toml::Value)>) -> String {
+    let fields: Vec<String> = fields
+        .map(|(key, value)| format!("readonly {}: {}", format_ts_key(key), ts_value_type(value)))
+        .collect();
+    if fields.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", fields.join("; "))
+    }
+}
+
+fn render_ts_node(
+    out: &mut String,
+    node: &CodegenNode,
+    depth: usize,
+    record_type: &str,
+    options: &RenderOptions,
+) {
     let indent = "\t".repeat(depth);
     match node {
+        CodegenNode::Leaf(leaf) if options.records && !leaf.data.is_empty() => {
+            out.push_str(&format!(
+                "{record_type} & {{ readonly data: {} }}\n",
+                ts_data_type(leaf.data.iter())
+            ));
+        }
+        CodegenNode::Leaf(_) if options.records => {
+            out.push_str(&format!("{record_type}\n"));
+        }
         CodegenNode::Leaf(_) => {
             out.push_str("number\n");
         }
@@ -303,39 +1191,294 @@ fn render_ts_node(out: &mut String, node: &CodegenNode, depth: usize) {
             out.push_str("{\n");
             for (key, child) in children {
                 out.push_str(&format!("{indent}\t{}: ", format_ts_key(key)));
-                render_ts_node(out, child, depth + 1);
+                render_ts_node(out, child, depth + 1, record_type, options);
             }
             out.push_str(&format!("{indent}}}\n"));
         }
     }
 }
 
+/// Render the object type of the generated table, including `byId`.
+fn render_ts_object(
+    out: &mut String,
+    tree: &CodegenTree,
+    record_type: &str,
+    options: &RenderOptions,
+) -> Result<()> {
+    out.push_str("{\n");
+    for (key, node) in tree {
+        out.push_str(&format!("\t{}: ", format_ts_key(key)));
+        render_ts_node(out, node, 1, record_type, options);
+    }
+
+    if let Some(mode) = options.reverse {
+        let values: BTreeSet<String> = reverse_lookup(tree, mode)?.into_values().collect();
+        let value_type = if values.is_empty() {
+            "string".to_string()
+        } else {
+            key_union(values.iter().map(String::as_str))
+        };
+        out.push_str(&format!(
+            "\t{REVERSE_FIELD}: {{\n\t\treadonly [id: number]: {value_type}\n\t}}\n"
+        ));
+    }
+
+    out.push_str("}\n");
+    Ok(())
+}
+
+/// `PassKey`/`BadgeKey`/`ProductKey` unions, one line each prefixed by `prefix`.
+fn render_ts_key_types(out: &mut String, tree: &CodegenTree, prefix: &str) {
+    for (label, ids) in resource_ids(tree) {
+        out.push_str(&format!(
+            "{prefix}type {label}Key = {}\n",
+            key_union(ids.keys().copied())
+        ));
+    }
+}
+
 pub fn generate_typescript(tree: &CodegenTree, output_path: &Path) -> Result<()> {
-    let var_name = output_path
+    generate_typescript_with(tree, output_path, &RenderOptions::default())
+}
+
+pub fn generate_typescript_with(
+    tree: &CodegenTree,
+    output_path: &Path,
+    options: &RenderOptions,
+) -> Result<()> {
+    let var_name = typescript_var_name(output_path);
+    write_output(output_path, &render_typescript(tree, &var_name, options)?)
+}
+
+/// Variable name declared by a `.d.ts` file: its name without extensions.
+fn typescript_var_name(output_path: &Path) -> String {
+    output_path
         .file_stem()
         .and_then(|s| s.to_str())
         // Strip .d from "Assets.d.ts" → "Assets"
         .map(|s| s.strip_suffix(".d").unwrap_or(s))
+        .unwrap_or("Assets")
+        .to_string()
+}
+
+/// Render the `.d.ts` declarations for a Luau module storing `tree` in `var_name`.
+pub fn render_typescript(
+    tree: &CodegenTree,
+    var_name: &str,
+    options: &RenderOptions,
+) -> Result<String> {
+    let mut out = String::new();
+    out.push_str("// This file is auto-generated by rbxsync. Do not edit manually.\n\n");
+    out.push_str(&format!("declare const {}: ", var_name));
+    render_ts_object(&mut out, tree, &format!("{var_name}.AssetRecord"), options)?;
+    out.push('\n');
+
+    // Type-only members merge with the const without changing the runtime shape.
+    if options.records {
+        out.push_str(&format!("declare namespace {} {{\n", var_name));
+        out.push_str(&ts_record_type("\t", ""));
+        out.push_str("}\n\n");
+    }
+    out.push_str(&format!("export = {}\n", var_name));
+
+    Ok(out)
+}
+
+/// Generate a roblox-ts package: `init.luau` with the Luau module and
+/// `index.d.ts` with its typings. The table is exported under the name of
+/// `package_dir`, alongside key unions and `const enum` ID maps.
+pub fn generate_roblox_ts(
+    tree: &CodegenTree,
+    package_dir: &Path,
+    options: &RenderOptions,
+) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for (path, content) in render_roblox_ts(tree, package_dir, options)? {
+        write_output(&path, &content)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Render the files of the roblox-ts package in `package_dir`, paired with
+/// the paths they belong at.
+pub fn render_roblox_ts(
+    tree: &CodegenTree,
+    package_dir: &Path,
+    options: &RenderOptions,
+) -> Result<Vec<(PathBuf, String)>> {
+    let var_name = package_dir
+        .file_name()
+        .and_then(|s| s.to_str())
         .unwrap_or("Assets");
 
+    let luau = render_luau_module(
+        tree,
+        var_name,
+        options,
+        &format!("{{ {var_name} = {var_name} }}"),
+    )?;
+
     let mut out = String::new();
     out.push_str("// This file is auto-generated by rbxsync. Do not edit manually.\n\n");
-    out.push_str(&format!("declare const {}: {{\n", var_name));
+    render_ts_key_types(&mut out, tree, "export ");
+    out.push('\n');
 
-    for (key, node) in tree {
-        out.push_str(&format!("\t{}: ", format_ts_key(key)));
-        render_ts_node(&mut out, node, 1);
+    for (label, ids) in resource_ids(tree) {
+        out.push_str(&format!("export declare const enum {label}Id {{\n"));
+        let mut members: BTreeMap<String, &str> = BTreeMap::new();
+        for (key, id) in ids {
+            let member = ts_enum_member(key);
+            if let Some(other) = members.insert(member.clone(), key) {
+                bail!(
+                    "{label}Id enum members for keys '{other}' and '{key}' would both be named {member}"
+                );
+            }
+            out.push_str(&format!("\t{member} = {id},\n"));
+        }
+        out.push_str("}\n\n");
     }
 
-    out.push_str("}\n\n");
-    out.push_str(&format!("export = {}\n", var_name));
+    if options.records {
+        out.push_str(&ts_record_type("", "export "));
+        out.push('\n');
+    }
 
-    if let Some(parent) = output_path.parent() {
-        std::fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    out.push_str(&format!("export declare const {var_name}: "));
+    render_ts_object(&mut out, tree, "AssetRecord", options)?;
+
+    Ok(vec![
+        (package_dir.join("init.luau"), luau),
+        (package_dir.join("index.d.ts"), out),
+    ])
+}
+
+// ---------------------------------------------------------------------------
+// Entry point
+// ---------------------------------------------------------------------------
+
+/// Narrow every `[[codegen.outputs]]` entry to what `selection` matches, on
+/// top of its own filters. The main outputs always cover every resource.
+pub fn select_outputs(config: &mut Config, selection: &Selection) {
+    for output in &mut config.codegen.outputs {
+        let narrowed = output
+            .select
+            .take()
+            .unwrap_or_default()
+            .and(selection.clone());
+        output.select = Some(narrowed);
+    }
+}
+
+/// Render every configured output for `lockfile` without touching the disk,
+/// pairing each file's contents with the path it is written to.
+pub fn render(
+    lockfile: &Lockfile,
+    config: &Config,
+    config_dir: &Path,
+) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let codegen = &config.codegen;
+    let options = RenderOptions::from(codegen);
+    let tree = build_tree(lockfile, config)?;
+    let output = resolve_output(config, config_dir)?;
+    let mut files = Vec::new();
+
+    if let Some(output_path) = output.clone() {
+        let var_name = output_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Assets");
+        let content = render_luau(&tree, var_name, &options)?;
+        files.push((output_path, content.into_bytes()));
     }
-    std::fs::write(output_path, &out)
-        .with_context(|| format!("Failed to write {}", output_path.display()))?;
 
-    Ok(())
+    let ts_path = match (&codegen.typescript_output, &output) {
+        (Some(ts_output), _) => Some(config_dir.join(ts_output)),
+        (None, Some(output)) if codegen.typescript => Some(output.with_extension("d.ts")),
+        _ => None,
+    };
+    if let Some(ts_path) = ts_path {
+        let content = render_typescript(&tree, &typescript_var_name(&ts_path), &options)?;
+        files.push((ts_path, content.into_bytes()));
+    }
+
+    if let Some(package_dir) = &codegen.roblox_ts {
+        let package = render_roblox_ts(&tree, &config_dir.join(package_dir), &options)?;
+        files.extend(
+            package
+                .into_iter()
+                .map(|(path, content)| (path, content.into_bytes())),
+        );
+    }
+
+    if let Some(model) = &codegen.model {
+        let model_path = config_dir.join(model);
+        let content = render_model(&tree, &model_path, &options)?;
+        files.push((model_path, content));
+    }
+
+    if let Some(marketplace) = &codegen.marketplace {
+        let content = render_marketplace(&tree);
+        files.push((config_dir.join(marketplace), content.into_bytes()));
+    }
+
+    for output in &codegen.outputs {
+        let tree = build_output_tree(lockfile, config, output)?;
+        let path = config_dir.join(&output.path);
+        match output.language {
+            CodegenLanguage::Luau => {
+                let var_name = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("Assets");
+                let content = render_luau(&tree, var_name, &options)?;
+                files.push((path, content.into_bytes()));
+            }
+            CodegenLanguage::Typescript => {
+                let content = render_typescript(&tree, &typescript_var_name(&path), &options)?;
+                files.push((path, content.into_bytes()));
+            }
+            CodegenLanguage::RobloxTs => {
+                let package = render_roblox_ts(&tree, &path, &options)?;
+                files.extend(
+                    package
+                        .into_iter()
+                        .map(|(path, content)| (path, content.into_bytes())),
+                );
+            }
+            CodegenLanguage::Model => {
+                let content = render_model(&tree, &path, &options)?;
+                files.push((path, content));
+            }
+        }
+    }
+
+    for template in &codegen.templates {
+        let output_path = config_dir.join(&template.output);
+        let content = render_template_file(
+            &tree,
+            lockfile,
+            &config_dir.join(&template.template),
+            &output_path,
+        )?;
+        files.push((output_path, content.into_bytes()));
+    }
+
+    if let Some(manifest) = &codegen.manifest {
+        let manifest_path = config_dir.join(manifest);
+        let content = render_manifest(lockfile, config, &manifest_path)?;
+        files.push((manifest_path, content.into_bytes()));
+    }
+
+    Ok(files)
+}
+
+/// Generate every configured output for `lockfile`, returning the paths written.
+pub fn generate(lockfile: &Lockfile, config: &Config, config_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for (path, content) in render(lockfile, config, config_dir)? {
+        write_bytes(&path, &content)?;
+        written.push(path);
+    }
+    Ok(written)
 }
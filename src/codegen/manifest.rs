@@ -0,0 +1,144 @@
+//! Language-neutral manifest of every synced resource, for services that
+//! validate purchases outside of Roblox.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::write_output;
+use crate::config::Config;
+use crate::lockfile::Lockfile;
+
+/// Bumped whenever a field is renamed, removed or changes meaning. Adding
+/// fields does not change the version.
+pub const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct Manifest {
+    pub version: u32,
+    pub universe_id: u64,
+    pub resources: Vec<ManifestResource>,
+    pub extra: Vec<ManifestExtra>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestResourceType {
+    Pass,
+    Badge,
+    Product,
+}
+
+/// A synced resource. Fields that don't apply to a resource type are `null`.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct ManifestResource {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub resource_type: ManifestResourceType,
+    pub id: u64,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: Option<u64>,
+    pub for_sale: Option<bool>,
+    pub enabled: Option<bool>,
+    pub icon_asset_id: Option<u64>,
+}
+
+/// An entry from `codegen.extra`.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct ManifestExtra {
+    pub key: String,
+    pub id: u64,
+}
+
+impl Manifest {
+    pub fn new(lockfile: &Lockfile, config: &Config) -> Self {
+        let mut resources = Vec::new();
+
+        for (key, lock) in &lockfile.passes {
+            resources.push(ManifestResource {
+                key: key.clone(),
+                resource_type: ManifestResourceType::Pass,
+                id: lock.id,
+                name: lock.name.clone(),
+                description: lock.description.clone(),
+                price: lock.price,
+                for_sale: Some(lock.for_sale),
+                enabled: None,
+                icon_asset_id: lock.icon_asset_id,
+            });
+        }
+        for (key, lock) in &lockfile.badges {
+            resources.push(ManifestResource {
+                key: key.clone(),
+                resource_type: ManifestResourceType::Badge,
+                id: lock.id,
+                name: lock.name.clone(),
+                description: lock.description.clone(),
+                price: None,
+                for_sale: None,
+                enabled: Some(lock.enabled),
+                icon_asset_id: lock.icon_asset_id,
+            });
+        }
+        for (key, lock) in &lockfile.products {
+            resources.push(ManifestResource {
+                key: key.clone(),
+                resource_type: ManifestResourceType::Product,
+                id: lock.id,
+                name: lock.name.clone(),
+                description: lock.description.clone(),
+                price: Some(lock.price),
+                for_sale: Some(lock.for_sale),
+                enabled: None,
+                icon_asset_id: lock.icon_asset_id,
+            });
+        }
+
+        let extra = config
+            .codegen
+            .extra
+            .iter()
+            .map(|(key, &id)| ManifestExtra {
+                key: key.clone(),
+                id,
+            })
+            .collect();
+
+        Self {
+            version: MANIFEST_VERSION,
+            universe_id: lockfile.universe_id,
+            resources,
+            extra,
+        }
+    }
+}
+
+/// Write the manifest as YAML if `output_path` ends in `.yaml`/`.yml`, JSON otherwise.
+pub fn generate_manifest(lockfile: &Lockfile, config: &Config, output_path: &Path) -> Result<()> {
+    write_output(
+        output_path,
+        &render_manifest(lockfile, config, output_path)?,
+    )
+}
+
+/// Render the manifest in the format `generate_manifest` picks for `output_path`.
+pub fn render_manifest(lockfile: &Lockfile, config: &Config, output_path: &Path) -> Result<String> {
+    let manifest = Manifest::new(lockfile, config);
+    let yaml = matches!(
+        output_path.extension().and_then(|e| e.to_str()),
+        Some("yaml" | "yml")
+    );
+
+    let content = if yaml {
+        serde_yaml::to_string(&manifest).context("Failed to serialize manifest")?
+    } else {
+        let mut json =
+            serde_json::to_string_pretty(&manifest).context("Failed to serialize manifest")?;
+        json.push('\n');
+        json
+    };
+
+    Ok(content)
+}
@@ -0,0 +1,141 @@
+//! Luau module wrapping `MarketplaceService` with typed helpers for the
+//! passes and products in a `CodegenTree`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Result;
+
+use super::{format_key, key_union, leaves, string_literal, write_output, CodegenTree, LeafSource};
+
+const HEADER: &str = "\
+--!strict
+-- This file is auto-generated by rbxsync. Do not edit manually.
+
+local MarketplaceService = game:GetService(\"MarketplaceService\")
+local Players = game:GetService(\"Players\")
+
+";
+
+const TYPES: &str = "\
+export type ReceiptInfo = {
+\tPurchaseId: string,
+\tPlayerId: number,
+\tProductId: number,
+\tPlaceIdWherePurchased: number,
+\tCurrencySpent: number,
+\tCurrencyType: Enum.CurrencyType,
+}
+
+-- Return true once the purchase has been granted and saved.
+export type ReceiptHandler = (player: Player, receiptInfo: ReceiptInfo) -> boolean
+
+";
+
+const BODY: &str = "\
+local handlers: { [string]: ReceiptHandler } = {}
+
+local Marketplace = {}
+
+function Marketplace.promptProduct(player: Player, key: ProductKey)
+\tMarketplaceService:PromptProductPurchase(player, productIds[key])
+end
+
+function Marketplace.promptPass(player: Player, key: PassKey)
+\tMarketplaceService:PromptGamePassPurchase(player, passIds[key])
+end
+
+-- Yields. Errors if the ownership check fails.
+function Marketplace.ownsPass(player: Player, key: PassKey): boolean
+\treturn MarketplaceService:UserOwnsGamePassAsync(player.UserId, passIds[key])
+end
+
+-- Registers the handler that grants the product `key`. Replaces any previous handler.
+function Marketplace.onProductPurchased(key: ProductKey, handler: ReceiptHandler)
+\thandlers[key] = handler
+end
+
+-- Routes a receipt to the handler registered for its product.
+function Marketplace.processReceipt(receiptInfo: ReceiptInfo): Enum.ProductPurchaseDecision
+\tlocal key = productKeys[receiptInfo.ProductId]
+\tlocal handler = if key then handlers[key] else nil
+\tif not handler then
+\t\twarn(`No handler registered for product {receiptInfo.ProductId}`)
+\t\treturn Enum.ProductPurchaseDecision.NotProcessedYet
+\tend
+
+\tlocal player = Players:GetPlayerByUserId(receiptInfo.PlayerId)
+\tif not player then
+\t\treturn Enum.ProductPurchaseDecision.NotProcessedYet
+\tend
+
+\tlocal ok, granted = pcall(handler, player, receiptInfo)
+\tif not ok then
+\t\twarn(`Handler for product '{key}' failed: {granted}`)
+\telseif granted then
+\t\treturn Enum.ProductPurchaseDecision.PurchaseGranted
+\tend
+\treturn Enum.ProductPurchaseDecision.NotProcessedYet
+end
+
+-- Installs `processReceipt` as `MarketplaceService.ProcessReceipt`. Server only.
+function Marketplace.bindProcessReceipt()
+\tMarketplaceService.ProcessReceipt = Marketplace.processReceipt
+end
+
+return Marketplace
+";
+
+fn id_table(name: &str, keys: &BTreeMap<String, u64>) -> String {
+    let mut out = format!("local {name}: {{ [string]: number }} = {{\n");
+    for (key, id) in keys {
+        out.push_str(&format!("\t{} = {id},\n", format_key(key)));
+    }
+    out.push_str("}\n\n");
+    out
+}
+
+/// Generates the `MarketplaceService` wrapper for the passes and products in
+/// `tree`. Entries from `codegen.extra` are not included.
+pub fn generate_marketplace(tree: &CodegenTree, output_path: &Path) -> Result<()> {
+    write_output(output_path, &render_marketplace(tree))
+}
+
+/// Render the `MarketplaceService` wrapper module for `tree`.
+pub fn render_marketplace(tree: &CodegenTree) -> String {
+    let mut passes = BTreeMap::new();
+    let mut products = BTreeMap::new();
+    for (_, leaf) in leaves(tree) {
+        match &leaf.source {
+            LeafSource::Pass(key) => {
+                passes.insert(key.clone(), leaf.id);
+            }
+            LeafSource::Product(key) => {
+                products.insert(key.clone(), leaf.id);
+            }
+            LeafSource::Badge(_) | LeafSource::Extra(_) => {}
+        }
+    }
+
+    let mut out = String::from(HEADER);
+    out.push_str(&format!(
+        "export type PassKey = {}\n",
+        key_union(passes.keys().map(String::as_str))
+    ));
+    out.push_str(&format!(
+        "export type ProductKey = {}\n",
+        key_union(products.keys().map(String::as_str))
+    ));
+    out.push_str(TYPES);
+    out.push_str(&id_table("passIds", &passes));
+    out.push_str(&id_table("productIds", &products));
+
+    out.push_str("local productKeys: { [number]: ProductKey } = {\n");
+    for (key, id) in &products {
+        out.push_str(&format!("\t[{id}] = {},\n", string_literal(key)));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(BODY);
+    out
+}
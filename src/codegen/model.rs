@@ -0,0 +1,152 @@
+//! Roblox model files (`.rbxmx` XML or binary `.rbxm`) holding the generated
+//! Luau module as a ModuleScript, for places that aren't managed by Rojo.
+
+use std::path::Path;
+
+use anyhow::{bail, Result};
+
+use super::{render_luau, write_bytes, CodegenTree, RenderOptions};
+
+/// Model format, chosen from the output file's extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelFormat {
+    /// `.rbxmx`
+    Xml,
+    /// `.rbxm`
+    Binary,
+}
+
+impl ModelFormat {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("rbxmx") => Ok(ModelFormat::Xml),
+            Some("rbxm") => Ok(ModelFormat::Binary),
+            _ => bail!(
+                "Model output {} must end in .rbxm or .rbxmx",
+                path.display()
+            ),
+        }
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// An XML model with a single ModuleScript.
+pub fn render_rbxmx(name: &str, source: &str) -> String {
+    // `]]>` can't appear inside CDATA, so split it across two sections.
+    let cdata = source.replace("]]>", "]]]]><![CDATA[>");
+    format!(
+        "<roblox version=\"4\">\n\
+         \t<Item class=\"ModuleScript\" referent=\"RBX0\">\n\
+         \t\t<Properties>\n\
+         \t\t\t<string name=\"Name\">{}</string>\n\
+         \t\t\t<ProtectedString name=\"Source\"><![CDATA[{cdata}]]></ProtectedString>\n\
+         \t\t</Properties>\n\
+         \t</Item>\n\
+         </roblox>\n",
+        escape_xml(name)
+    )
+}
+
+const BINARY_MAGIC: &[u8] = b"<roblox!\x89\xff\x0d\x0a\x1a\x0a";
+const TYPE_STRING: u8 = 0x01;
+
+fn push_string(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// Referent arrays are zigzag-encoded, delta-encoded and byte-interleaved.
+fn push_referents(out: &mut Vec<u8>, referents: &[i32]) {
+    let mut previous = 0;
+    let encoded: Vec<[u8; 4]> = referents
+        .iter()
+        .map(|&referent| {
+            let delta = referent - previous;
+            previous = referent;
+            (((delta << 1) ^ (delta >> 31)) as u32).to_be_bytes()
+        })
+        .collect();
+    for byte in 0..4 {
+        out.extend(encoded.iter().map(|bytes| bytes[byte]));
+    }
+}
+
+/// Append an uncompressed chunk.
+fn push_chunk(out: &mut Vec<u8>, name: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(name);
+    out.extend_from_slice(&0u32.to_le_bytes()); // compressed length: stored uncompressed
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes()); // reserved
+    out.extend_from_slice(data);
+}
+
+/// A binary model with a single ModuleScript.
+pub fn render_rbxm(name: &str, source: &str) -> Vec<u8> {
+    let mut out = Vec::from(BINARY_MAGIC);
+    out.extend_from_slice(&0u16.to_le_bytes()); // version
+    out.extend_from_slice(&1u32.to_le_bytes()); // class count
+    out.extend_from_slice(&1u32.to_le_bytes()); // instance count
+    out.extend_from_slice(&[0; 8]); // reserved
+
+    let mut inst = Vec::new();
+    inst.extend_from_slice(&0u32.to_le_bytes()); // class ID
+    push_string(&mut inst, "ModuleScript");
+    inst.push(0); // not a service
+    inst.extend_from_slice(&1u32.to_le_bytes());
+    push_referents(&mut inst, &[0]);
+    push_chunk(&mut out, b"INST", &inst);
+
+    for (property, value) in [("Name", name), ("Source", source)] {
+        let mut prop = Vec::new();
+        prop.extend_from_slice(&0u32.to_le_bytes()); // class ID
+        push_string(&mut prop, property);
+        prop.push(TYPE_STRING);
+        push_string(&mut prop, value);
+        push_chunk(&mut out, b"PROP", &prop);
+    }
+
+    let mut prnt = Vec::new();
+    prnt.push(0); // version
+    prnt.extend_from_slice(&1u32.to_le_bytes());
+    push_referents(&mut prnt, &[0]);
+    push_referents(&mut prnt, &[-1]); // no parent: top level of the model
+    push_chunk(&mut out, b"PRNT", &prnt);
+
+    push_chunk(&mut out, b"END\0", b"</roblox>");
+    out
+}
+
+/// Render a model file containing the Luau module for `tree`. The
+/// ModuleScript is named after the file, and the format follows its extension.
+pub fn render_model(
+    tree: &CodegenTree,
+    output_path: &Path,
+    options: &RenderOptions,
+) -> Result<Vec<u8>> {
+    let format = ModelFormat::from_path(output_path)?;
+    let name = output_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Assets");
+    let source = render_luau(tree, name, options)?;
+
+    Ok(match format {
+        ModelFormat::Xml => render_rbxmx(name, &source).into_bytes(),
+        ModelFormat::Binary => render_rbxm(name, &source),
+    })
+}
+
+/// Write a `.rbxm`/`.rbxmx` model containing the Luau module for `tree`.
+pub fn generate_model(
+    tree: &CodegenTree,
+    output_path: &Path,
+    options: &RenderOptions,
+) -> Result<()> {
+    write_bytes(output_path, &render_model(tree, output_path, options)?)
+}
@@ -0,0 +1,245 @@
+//! Rojo project awareness: checks that generated modules land where Rojo
+//! syncs them, and derives output files from instance paths.
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use crate::config::{CodegenLanguage, Config};
+
+/// Project file used when `codegen.rojo_project` isn't set.
+pub const DEFAULT_PROJECT: &str = "default.project.json";
+
+/// Extensions Rojo turns into a ModuleScript.
+const MODULE_EXTENSIONS: &[&str] = &["luau", "lua"];
+
+/// An instance whose contents Rojo syncs from a `$path`.
+#[derive(Debug)]
+struct Mount {
+    instance: Vec<String>,
+    path: PathBuf,
+}
+
+impl Mount {
+    fn is_file(&self) -> bool {
+        self.path.extension().is_some()
+    }
+}
+
+#[derive(Debug)]
+pub struct RojoProject {
+    path: PathBuf,
+    mounts: Vec<Mount>,
+}
+
+/// Resolve `.` and `..` without touching the filesystem, since generated
+/// files may not exist yet.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push("..");
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+fn collect_mounts(node: &Value, instance: &mut Vec<String>, dir: &Path, out: &mut Vec<Mount>) {
+    let Some(object) = node.as_object() else {
+        return;
+    };
+
+    let path = match object.get("$path") {
+        Some(Value::String(path)) => Some(path.as_str()),
+        Some(Value::Object(optional)) => optional.get("optional").and_then(Value::as_str),
+        _ => None,
+    };
+    if let Some(path) = path {
+        out.push(Mount {
+            instance: instance.clone(),
+            path: normalize(&dir.join(path)),
+        });
+    }
+
+    for (name, child) in object {
+        if name.starts_with('$') {
+            continue;
+        }
+        instance.push(name.clone());
+        collect_mounts(child, instance, dir, out);
+        instance.pop();
+    }
+}
+
+impl RojoProject {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read Rojo project {}", path.display()))?;
+        let project: Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse Rojo project {}", path.display()))?;
+        let Some(tree) = project.get("tree") else {
+            bail!("Rojo project {} has no \"tree\"", path.display());
+        };
+
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let mut mounts = Vec::new();
+        collect_mounts(tree, &mut Vec::new(), dir, &mut mounts);
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            mounts,
+        })
+    }
+
+    /// The project named by `codegen.rojo_project`, or `default.project.json`
+    /// next to the config if it exists.
+    pub fn find(config: &Config, config_dir: &Path) -> Result<Option<Self>> {
+        match &config.codegen.rojo_project {
+            Some(path) => Self::load(&config_dir.join(path)).map(Some),
+            None => {
+                let path = config_dir.join(DEFAULT_PROJECT);
+                if path.exists() {
+                    Self::load(&path).map(Some)
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The dotted instance path Rojo syncs `file` to, if any.
+    pub fn instance_for_file(&self, file: &Path) -> Option<String> {
+        let file = normalize(file);
+        let (mount, rest) = self
+            .mounts
+            .iter()
+            .filter_map(|mount| {
+                let rest = file.strip_prefix(&mount.path).ok()?;
+                (rest.as_os_str().is_empty() || !mount.is_file()).then_some((mount, rest))
+            })
+            .max_by_key(|(mount, _)| mount.path.components().count())?;
+
+        let mut instance = mount.instance.clone();
+        let mut components: Vec<String> = rest
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        if let Some(last) = components.pop() {
+            let stem = Path::new(&last)
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or(last);
+            components.extend((stem != "init").then_some(stem));
+        }
+        instance.extend(components);
+        Some(instance.join("."))
+    }
+
+    /// The file that Rojo would sync to the ModuleScript at `instance`, e.g.
+    /// `ReplicatedStorage.Shared.GameIds` → `src/shared/GameIds.luau`.
+    pub fn file_for_instance(&self, instance: &str) -> Result<PathBuf> {
+        let segments: Vec<&str> = instance.split('.').collect();
+
+        let exact = self.mounts.iter().find(|mount| {
+            mount.instance == segments
+                && mount
+                    .path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| MODULE_EXTENSIONS.contains(&e))
+        });
+        if let Some(mount) = exact {
+            return Ok(mount.path.clone());
+        }
+
+        let parent = self
+            .mounts
+            .iter()
+            .filter(|mount| {
+                !mount.is_file()
+                    && mount.instance.len() < segments.len()
+                    && mount.instance.iter().zip(&segments).all(|(a, b)| a == b)
+            })
+            .max_by_key(|mount| mount.instance.len());
+        let Some(mount) = parent else {
+            bail!(
+                "{instance} isn't inside any $path in Rojo project {}",
+                self.path.display()
+            );
+        };
+
+        let (name, dirs) = segments[mount.instance.len()..]
+            .split_last()
+            .expect("instance is longer than the mount");
+        let mut path = mount.path.clone();
+        path.extend(dirs);
+        path.push(format!("{name}.luau"));
+        Ok(path)
+    }
+}
+
+/// Where the main Luau module is written: `codegen.output`, or the file Rojo
+/// syncs to `codegen.output_instance`.
+pub fn resolve_output(config: &Config, config_dir: &Path) -> Result<Option<PathBuf>> {
+    let codegen = &config.codegen;
+    match (&codegen.output, &codegen.output_instance) {
+        (Some(_), Some(_)) => {
+            bail!("Set either codegen.output or codegen.output_instance, not both")
+        }
+        (Some(output), None) => Ok(Some(config_dir.join(output))),
+        (None, Some(instance)) => {
+            let Some(project) = RojoProject::find(config, config_dir)? else {
+                bail!(
+                    "codegen.output_instance needs a Rojo project: add {DEFAULT_PROJECT} or set codegen.rojo_project"
+                );
+            };
+            project.file_for_instance(instance).map(Some)
+        }
+        (None, None) => Ok(None),
+    }
+}
+
+/// Warnings for generated Luau modules that Rojo won't sync. Empty when
+/// there's no Rojo project.
+pub fn rojo_warnings(config: &Config, config_dir: &Path) -> Result<Vec<String>> {
+    let Some(project) = RojoProject::find(config, config_dir)? else {
+        return Ok(Vec::new());
+    };
+    let codegen = &config.codegen;
+
+    let mut modules = Vec::new();
+    if let Some(output) = &codegen.output {
+        modules.push(("codegen.output", output));
+    }
+    if let Some(marketplace) = &codegen.marketplace {
+        modules.push(("codegen.marketplace", marketplace));
+    }
+    for output in &codegen.outputs {
+        if output.language == CodegenLanguage::Luau {
+            modules.push(("codegen.outputs", &output.path));
+        }
+    }
+
+    Ok(modules
+        .into_iter()
+        .filter(|(_, path)| project.instance_for_file(&config_dir.join(path)).is_none())
+        .map(|(field, path)| {
+            format!(
+                "{field} {} isn't inside any path synced by {}, so Rojo won't pick it up",
+                path.display(),
+                project.path().display()
+            )
+        })
+        .collect())
+}
@@ -0,0 +1,185 @@
+//! User-defined codegen templates rendered with MiniJinja.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use minijinja::{AutoEscape, Environment, UndefinedBehavior};
+use serde::Serialize;
+
+use super::{
+    format_key, format_ts_key, icon_uri, is_valid_luau_identifier, is_valid_ts_identifier, leaves,
+    string_literal, write_output, CodegenLeaf, CodegenNode, CodegenTree, LeafSource,
+};
+use crate::lockfile::Lockfile;
+
+/// A leaf as seen by templates.
+#[derive(Debug, Serialize)]
+struct TemplateEntry {
+    path: String,
+    key: String,
+    config_key: String,
+    #[serde(rename = "type")]
+    resource_type: &'static str,
+    id: u64,
+    name: Option<String>,
+    price: Option<u64>,
+    description: Option<String>,
+    icon_id: Option<String>,
+    for_sale: Option<bool>,
+    data: BTreeMap<String, toml::Value>,
+}
+
+impl TemplateEntry {
+    fn new(path: String, leaf: &CodegenLeaf) -> Self {
+        Self {
+            path,
+            key: leaf.key.clone(),
+            config_key: leaf.source.key().to_string(),
+            resource_type: match leaf.source {
+                LeafSource::Pass(_) => "pass",
+                LeafSource::Badge(_) => "badge",
+                LeafSource::Product(_) => "product",
+                LeafSource::Extra(_) => "extra",
+            },
+            id: leaf.id,
+            name: leaf.name.clone(),
+            price: leaf.price,
+            description: leaf.description.clone(),
+            icon_id: leaf.icon_asset_id.map(icon_uri),
+            for_sale: leaf.for_sale,
+            data: leaf.data.clone(),
+        }
+    }
+}
+
+/// A tree node as seen by templates. Leaves have an `entry`, branches have `children`.
+#[derive(Debug, Serialize)]
+struct TemplateNode {
+    key: String,
+    path: String,
+    entry: Option<TemplateEntry>,
+    children: Vec<TemplateNode>,
+}
+
+fn template_nodes(nodes: &CodegenTree, prefix: &str) -> Vec<TemplateNode> {
+    nodes
+        .iter()
+        .map(|(key, node)| {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            match node {
+                CodegenNode::Leaf(leaf) => TemplateNode {
+                    key: key.clone(),
+                    entry: Some(TemplateEntry::new(path.clone(), leaf)),
+                    path,
+                    children: Vec::new(),
+                },
+                CodegenNode::Branch(children) => TemplateNode {
+                    key: key.clone(),
+                    children: template_nodes(children, &path),
+                    path,
+                    entry: None,
+                },
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+struct TemplateContext {
+    name: String,
+    universe_id: u64,
+    tree: Vec<TemplateNode>,
+    entries: Vec<TemplateEntry>,
+    passes: Vec<TemplateEntry>,
+    badges: Vec<TemplateEntry>,
+    products: Vec<TemplateEntry>,
+    extra: Vec<TemplateEntry>,
+}
+
+fn environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env.set_auto_escape_callback(|_| AutoEscape::None);
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_keep_trailing_newline(true);
+
+    env.add_filter("luau_key", |key: String| format_key(&key));
+    env.add_filter("ts_key", |key: String| format_ts_key(&key));
+    env.add_filter("luau_string", |s: String| string_literal(&s));
+    env.add_filter("ts_string", |s: String| string_literal(&s));
+    env.add_test("luau_identifier", |s: String| is_valid_luau_identifier(&s));
+    env.add_test("ts_identifier", |s: String| is_valid_ts_identifier(&s));
+    env
+}
+
+/// Render `source` against `tree` and `lockfile`. `name` is exposed to the
+/// template as the module name.
+pub fn render_template(
+    source: &str,
+    tree: &CodegenTree,
+    lockfile: &Lockfile,
+    name: &str,
+) -> Result<String> {
+    let entries: Vec<TemplateEntry> = leaves(tree)
+        .into_iter()
+        .map(|(path, leaf)| TemplateEntry::new(path, leaf))
+        .collect();
+    let of_type = |resource_type: &str| -> Vec<TemplateEntry> {
+        leaves(tree)
+            .into_iter()
+            .map(|(path, leaf)| TemplateEntry::new(path, leaf))
+            .filter(|entry| entry.resource_type == resource_type)
+            .collect()
+    };
+
+    let context = TemplateContext {
+        name: name.to_string(),
+        universe_id: lockfile.universe_id,
+        tree: template_nodes(tree, ""),
+        passes: of_type("pass"),
+        badges: of_type("badge"),
+        products: of_type("product"),
+        extra: of_type("extra"),
+        entries,
+    };
+
+    let env = environment();
+    let template = env.template_from_str(source)?;
+    Ok(template.render(context)?)
+}
+
+/// Render the template at `template_path` and write it to `output_path`.
+pub fn generate_template(
+    tree: &CodegenTree,
+    lockfile: &Lockfile,
+    template_path: &Path,
+    output_path: &Path,
+) -> Result<()> {
+    let content = render_template_file(tree, lockfile, template_path, output_path)?;
+    write_output(output_path, &content)
+}
+
+/// Render the template at `template_path` as it would be written to `output_path`.
+pub(super) fn render_template_file(
+    tree: &CodegenTree,
+    lockfile: &Lockfile,
+    template_path: &Path,
+    output_path: &Path,
+) -> Result<String> {
+    let source = std::fs::read_to_string(template_path)
+        .with_context(|| format!("Failed to read template {}", template_path.display()))?;
+    let name = output_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .and_then(|s| s.split('.').next())
+        .unwrap_or("Assets");
+
+    render_template(&source, tree, lockfile, name)
+        .with_context(|| format!("Failed to render template {}", template_path.display()))
+}
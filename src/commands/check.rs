@@ -4,15 +4,36 @@ use anyhow::Result;
 use colored::Colorize;
 
 use crate::cli::Cli;
+use crate::codegen;
 use crate::config::Config;
 use crate::diff::{build_sync_plan, Action};
+use crate::guardrails;
 use crate::lockfile::Lockfile;
+use crate::schedule;
+use crate::select::Selection;
 
 pub async fn run(cli: &Cli) -> Result<()> {
     // Validate config
-    let config = Config::load(&cli.config)?;
+    let mut config = Config::load(&cli.config)?;
     println!("{} Config is valid ({})", "✓".green(), cli.config.display());
 
+    let now = schedule::now();
+    for transition in schedule::upcoming_transitions(&config, now)? {
+        println!(
+            "{} {} product '{}' {} {}",
+            "ℹ".blue(),
+            transition.time,
+            transition.key,
+            if transition.starts {
+                "schedule applies"
+            } else {
+                "schedule reverts"
+            },
+            transition.overrides
+        );
+    }
+    schedule::apply(&mut config, now)?;
+
     let config_dir = cli.config.parent().unwrap_or(Path::new("."));
     let lockfile_path = config_dir.join(crate::lockfile::LOCKFILE_NAME);
 
@@ -41,11 +62,23 @@ pub async fn run(cli: &Cli) -> Result<()> {
         );
     }
 
-    let plan = build_sync_plan(&config, &lockfile, config_dir)?;
+    for collision in codegen::path_collisions(&lockfile, &config) {
+        println!("{} {:#}", "✗".red(), collision);
+    }
+    for warning in codegen::rojo_warnings(&config, config_dir)? {
+        println!("{} {}", "!".yellow(), warning);
+    }
+
+    let mut plan = build_sync_plan(&config, &lockfile, config_dir)?;
+    let selection = Selection::parse_all(&cli.select)?;
+    plan.retain(|section, key| selection.matches(section, key, config.tags(section, key)));
 
     for warning in &plan.warnings {
         println!("{} {}", "!".yellow(), warning);
     }
+    for violation in guardrails::price_violations(&plan, &config, &lockfile) {
+        println!("{} {}", "!".yellow(), violation);
+    }
 
     // Count changes
     let mut creates = 0;
@@ -62,7 +95,7 @@ pub async fn run(cli: &Cli) -> Result<()> {
         println!("{} Everything is in sync.", "✓".green());
     } else {
         println!(
-            "{} Out of sync: {} to create, {} to update. Run `rbxsync sync` or `rbxsync diff` for details.",
+            "{} Out of sync: {} to create, {} to update. Run `rbxsync sync --dry-run` for details.",
             "!".yellow(),
             creates,
             updates
@@ -0,0 +1,77 @@
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+
+use crate::cli::Cli;
+use crate::codegen;
+use crate::config::Config;
+use crate::lockfile::{Lockfile, LOCKFILE_NAME};
+use crate::select::Selection;
+
+pub fn run(cli: &Cli, check: bool) -> Result<()> {
+    let mut config = Config::load(&cli.config)?;
+    let config_dir = cli.config.parent().unwrap_or(Path::new("."));
+    let lockfile = Lockfile::load(&config_dir.join(LOCKFILE_NAME))?;
+
+    let selection = Selection::parse_all(&cli.select)?;
+    if !selection.is_empty() {
+        if check {
+            bail!("--select can't be combined with --check, which compares every generated file");
+        }
+        if config.codegen.outputs.is_empty() {
+            bail!("--select only narrows [[codegen.outputs]] entries, and none are configured");
+        }
+        codegen::select_outputs(&mut config, &selection);
+    }
+
+    for warning in codegen::rojo_warnings(&config, config_dir)? {
+        println!("{} {}", "!".yellow(), warning);
+    }
+
+    if !check {
+        let written = codegen::generate(&lockfile, &config, config_dir)?;
+        if written.is_empty() {
+            println!("{} No codegen outputs configured.", "!".yellow());
+        }
+        for path in written {
+            println!("{} Generated {}", "✓".green(), path.display());
+        }
+        return Ok(());
+    }
+
+    let files = codegen::render(&lockfile, &config, config_dir)?;
+    if files.is_empty() {
+        println!("{} No codegen outputs configured.", "!".yellow());
+        return Ok(());
+    }
+
+    let mut stale = 0;
+    for (path, expected) in &files {
+        match std::fs::read(path) {
+            Ok(actual) if actual == *expected => {}
+            Ok(_) => {
+                println!("{} {} is out of date", "✗".red(), path.display());
+                stale += 1;
+            }
+            Err(_) => {
+                println!("{} {} is missing", "✗".red(), path.display());
+                stale += 1;
+            }
+        }
+    }
+
+    if stale > 0 {
+        bail!(
+            "{stale} of {} generated file(s) differ from the lockfile. Run `rbxsync codegen` to regenerate them.",
+            files.len()
+        );
+    }
+
+    println!(
+        "{} All {} generated file(s) are up to date.",
+        "✓".green(),
+        files.len()
+    );
+    Ok(())
+}
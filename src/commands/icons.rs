@@ -0,0 +1,475 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+
+use crate::cli::Cli;
+use crate::config::{Config, ICON_EXTENSIONS};
+use crate::diff::hash_file;
+use crate::icon::{self, IconShape};
+use crate::lockfile::{Lockfile, LOCKFILE_NAME};
+
+/// A config entry along with its icon and lockfile state.
+struct IconEntry<'a> {
+    resource_type: &'static str,
+    key: &'a str,
+    path: Option<PathBuf>,
+    shape: IconShape,
+    icon_asset_id: Option<u64>,
+    icon_hash: Option<&'a str>,
+}
+
+fn icon_entries<'a>(
+    config: &'a Config,
+    lockfile: &'a Lockfile,
+    config_dir: &Path,
+) -> Vec<IconEntry<'a>> {
+    let mut entries = Vec::new();
+
+    for key in config.passes.keys() {
+        let lock = lockfile.passes.get(key);
+        entries.push(IconEntry {
+            resource_type: "pass",
+            key,
+            path: config.pass_icon(key, config_dir),
+            shape: IconShape::Square,
+            icon_asset_id: lock.and_then(|l| l.icon_asset_id),
+            icon_hash: lock.and_then(|l| l.icon_hash.as_deref()),
+        });
+    }
+    for key in config.badges.keys() {
+        let lock = lockfile.badges.get(key);
+        entries.push(IconEntry {
+            resource_type: "badge",
+            key,
+            path: config.badge_icon(key, config_dir),
+            shape: IconShape::Circle,
+            icon_asset_id: lock.and_then(|l| l.icon_asset_id),
+            icon_hash: lock.and_then(|l| l.icon_hash.as_deref()),
+        });
+    }
+    for key in config.products.keys() {
+        let lock = lockfile.products.get(key);
+        entries.push(IconEntry {
+            resource_type: "product",
+            key,
+            path: config.product_icon(key, config_dir),
+            shape: IconShape::Square,
+            icon_asset_id: lock.and_then(|l| l.icon_asset_id),
+            icon_hash: lock.and_then(|l| l.icon_hash.as_deref()),
+        });
+    }
+
+    entries
+}
+
+pub fn preview(cli: &Cli, output: &Path, columns: u32) -> Result<()> {
+    cli.reject_select("icons preview")?;
+
+    let config = Config::load(&cli.config)?;
+    let config_dir = cli.config.parent().unwrap_or(Path::new("."));
+    let lockfile = Lockfile::load(&config_dir.join(LOCKFILE_NAME))?;
+
+    let entries: Vec<(IconEntry, PathBuf)> = icon_entries(&config, &lockfile, config_dir)
+        .into_iter()
+        .filter_map(|mut e| e.path.take().map(|p| (e, p)))
+        .collect();
+    if entries.is_empty() {
+        bail!("No icons configured");
+    }
+
+    let mut tiles = Vec::with_capacity(entries.len());
+    let mut warnings = 0;
+
+    for (index, (entry, path)) in entries.iter().enumerate() {
+        let img = icon::load_icon(&config_dir.join(path), config.icons.bleed)?;
+
+        println!(
+            "  {:>3}. {} {} ({})",
+            index + 1,
+            entry.resource_type,
+            entry.key.bold(),
+            path.display()
+        );
+
+        if entry.shape == IconShape::Circle {
+            if let Some(warning) = icon::lint_badge_safe_area(&img.to_rgba8()) {
+                println!("       {} {}", "!".yellow(), warning);
+                warnings += 1;
+            }
+        }
+
+        tiles.push(icon::preview_tile(&img, entry.shape));
+    }
+
+    let sheet = icon::contact_sheet(&tiles, columns);
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    sheet
+        .save(output)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    if warnings > 0 {
+        println!(
+            "\n{} {} badge icon(s) have content outside the visible circle",
+            "!".yellow(),
+            warnings
+        );
+    }
+    println!("{} Generated {}", "✓".green(), output.display());
+
+    Ok(())
+}
+
+/// Problems found with an entry's icon during `icons report`.
+enum IconFlag {
+    NoIcon,
+    Missing,
+    NeverSynced,
+    Changed,
+    Invalid(String),
+    Cropped(String),
+}
+
+impl IconFlag {
+    fn label(&self) -> String {
+        match self {
+            IconFlag::NoIcon => "no icon".to_string(),
+            IconFlag::Missing => "icon file missing".to_string(),
+            IconFlag::NeverSynced => "never synced".to_string(),
+            IconFlag::Changed => "changed since last sync".to_string(),
+            IconFlag::Invalid(err) => format!("invalid: {err}"),
+            IconFlag::Cropped(warning) => warning.clone(),
+        }
+    }
+}
+
+struct ReportRow<'a> {
+    entry: IconEntry<'a>,
+    image_file: Option<String>,
+    flags: Vec<IconFlag>,
+}
+
+pub fn report(cli: &Cli, output: &Path) -> Result<()> {
+    cli.reject_select("icons report")?;
+
+    let config = Config::load_allow_missing_icons(&cli.config)?;
+    let config_dir = cli.config.parent().unwrap_or(Path::new("."));
+    let lockfile = Lockfile::load(&config_dir.join(LOCKFILE_NAME))?;
+
+    std::fs::create_dir_all(output)
+        .with_context(|| format!("Failed to create directory {}", output.display()))?;
+
+    let mut rows = Vec::new();
+    for (index, entry) in icon_entries(&config, &lockfile, config_dir)
+        .into_iter()
+        .enumerate()
+    {
+        let mut flags = Vec::new();
+        let mut image_file = None;
+
+        match &entry.path {
+            None => flags.push(IconFlag::NoIcon),
+            Some(path) if !config_dir.join(path).is_file() => flags.push(IconFlag::Missing),
+            Some(path) => {
+                let full_path = config_dir.join(path);
+                // An unreadable file is flagged as invalid when loading it below
+                match entry.icon_hash {
+                    None => flags.push(IconFlag::NeverSynced),
+                    Some(synced) => {
+                        if hash_file(&full_path).is_ok_and(|current| current != synced) {
+                            flags.push(IconFlag::Changed);
+                        }
+                    }
+                }
+
+                match icon::load_icon(&full_path, config.icons.bleed) {
+                    Ok(img) => {
+                        if entry.shape == IconShape::Circle {
+                            if let Some(warning) = icon::lint_badge_safe_area(&img.to_rgba8()) {
+                                flags.push(IconFlag::Cropped(warning));
+                            }
+                        }
+                        let file_name = format!("{}-{}.png", entry.resource_type, index);
+                        let tile = icon::preview_tile(&img, entry.shape);
+                        tile.save(output.join(&file_name)).with_context(|| {
+                            format!("Failed to write {}", output.join(&file_name).display())
+                        })?;
+                        image_file = Some(file_name);
+                    }
+                    Err(err) => flags.push(IconFlag::Invalid(format!("{err:#}"))),
+                }
+            }
+        }
+
+        if !flags.is_empty() {
+            println!(
+                "  {} {} {}: {}",
+                "!".yellow(),
+                entry.resource_type,
+                entry.key.bold(),
+                flags
+                    .iter()
+                    .map(IconFlag::label)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+
+        rows.push(ReportRow {
+            entry,
+            image_file,
+            flags,
+        });
+    }
+
+    let index_path = output.join("index.html");
+    std::fs::write(&index_path, render_report(&rows))
+        .with_context(|| format!("Failed to write {}", index_path.display()))?;
+
+    let flagged = rows.iter().filter(|r| !r.flags.is_empty()).count();
+    println!(
+        "{} Generated {} ({} resources, {} flagged)",
+        "✓".green(),
+        index_path.display(),
+        rows.len(),
+        flagged
+    );
+
+    Ok(())
+}
+
+fn render_report(rows: &[ReportRow]) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>rbxsync icon report</title>\n<style>\n");
+    out.push_str(
+        "body { font-family: sans-serif; background: #1e1f22; color: #e0e0e0; }\n\
+         .grid { display: flex; flex-wrap: wrap; gap: 16px; }\n\
+         .card { width: 180px; padding: 12px; background: #2b2d31; border-radius: 8px; }\n\
+         .card.flagged { outline: 2px solid #f0b232; }\n\
+         .card img, .card .empty { width: 150px; height: 150px; display: block; margin: 0 auto 8px; }\n\
+         .card .empty { border: 1px dashed #666; }\n\
+         .card .empty.missing { border-color: #f23f43; }\n\
+         .type { color: #999; font-size: 12px; }\n\
+         .key { font-weight: bold; word-break: break-all; }\n\
+         .meta { font-size: 12px; color: #aaa; }\n\
+         .flag { font-size: 12px; color: #f0b232; }\n",
+    );
+    out.push_str("</style>\n</head>\n<body>\n<h1>Icons</h1>\n<div class=\"grid\">\n");
+
+    for row in rows {
+        let class = if row.flags.is_empty() {
+            "card"
+        } else {
+            "card flagged"
+        };
+        out.push_str(&format!("<div class=\"{class}\">\n"));
+        match &row.image_file {
+            Some(file) => out.push_str(&format!("<img src=\"{}\">\n", escape_html(file))),
+            None if row.flags.iter().any(|f| matches!(f, IconFlag::Missing)) => {
+                out.push_str("<div class=\"empty missing\"></div>\n")
+            }
+            None => out.push_str("<div class=\"empty\"></div>\n"),
+        }
+        out.push_str(&format!(
+            "<div class=\"type\">{}</div>\n<div class=\"key\">{}</div>\n",
+            row.entry.resource_type,
+            escape_html(row.entry.key)
+        ));
+        if let Some(path) = &row.entry.path {
+            out.push_str(&format!(
+                "<div class=\"meta\">{}</div>\n",
+                escape_html(&path.display().to_string())
+            ));
+        }
+        let asset = row
+            .entry
+            .icon_asset_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!("<div class=\"meta\">asset: {asset}</div>\n"));
+        for flag in &row.flags {
+            out.push_str(&format!(
+                "<div class=\"flag\">{}</div>\n",
+                escape_html(&flag.label())
+            ));
+        }
+        out.push_str("</div>\n");
+    }
+
+    out.push_str("</div>\n</body>\n</html>\n");
+    out
+}
+
+pub fn gc(cli: &Cli, dry_run: bool, delete: bool, move_to: Option<&Path>) -> Result<()> {
+    cli.reject_select("icons gc")?;
+
+    let config = Config::load(&cli.config)?;
+    let config_dir = cli.config.parent().unwrap_or(Path::new("."));
+    let lockfile = Lockfile::load(&config_dir.join(LOCKFILE_NAME))?;
+    let icon_dir = config_dir.join(&config.icons.dir);
+
+    if !icon_dir.is_dir() {
+        println!(
+            "{} Icon directory {} does not exist.",
+            "✓".green(),
+            icon_dir.display()
+        );
+        return Ok(());
+    }
+
+    let referenced: HashSet<PathBuf> = icon_entries(&config, &lockfile, config_dir)
+        .into_iter()
+        .filter_map(|e| e.path)
+        .filter_map(|p| config_dir.join(p).canonicalize().ok())
+        .collect();
+    let move_to = move_to.map(|p| config_dir.join(p));
+    let move_to_canonical = match &move_to {
+        Some(dir) if !dry_run => {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+            Some(dir.canonicalize()?)
+        }
+        // A destination that doesn't exist yet can't hold any icons
+        Some(dir) if dir.exists() => Some(dir.canonicalize()?),
+        _ => None,
+    };
+
+    let mut files = Vec::new();
+    collect_images(&icon_dir, &mut files)?;
+    files.sort();
+
+    let mut orphans = Vec::new();
+    for file in files {
+        let canonical = file.canonicalize()?;
+        if referenced.contains(&canonical) {
+            continue;
+        }
+        if let Some(dest) = &move_to_canonical {
+            if canonical.starts_with(dest) {
+                continue;
+            }
+        }
+        let size = std::fs::metadata(&file)?.len();
+        orphans.push((file, size));
+    }
+
+    if orphans.is_empty() {
+        println!("{} No orphaned icons found.", "✓".green());
+        return Ok(());
+    }
+
+    let total: u64 = orphans.iter().map(|(_, size)| size).sum();
+    let verb = if delete {
+        "delete"
+    } else if move_to.is_some() {
+        "move"
+    } else {
+        "orphaned"
+    };
+
+    for (file, size) in &orphans {
+        let relative = file.strip_prefix(config_dir).unwrap_or(file);
+        println!(
+            "  {} {} {} ({})",
+            "-".red(),
+            verb.red(),
+            relative.display(),
+            format_size(*size)
+        );
+    }
+
+    if dry_run || (!delete && move_to.is_none()) {
+        println!(
+            "\n{} orphaned icon(s), {} total",
+            orphans.len(),
+            format_size(total)
+        );
+        if dry_run {
+            println!("\n{} Dry run — no changes applied.", "ℹ".blue());
+        } else {
+            println!("Use --delete or --move-to <dir> to clean them up.");
+        }
+        return Ok(());
+    }
+
+    for (file, _) in &orphans {
+        if delete {
+            std::fs::remove_file(file)
+                .with_context(|| format!("Failed to delete {}", file.display()))?;
+        } else if let Some(dest_dir) = &move_to {
+            let relative = file.strip_prefix(&icon_dir).unwrap_or(file);
+            let dest = dest_dir.join(relative);
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+            }
+            move_file(file, &dest)?;
+        }
+    }
+
+    println!(
+        "{} Reclaimed {} from {} icon(s)",
+        "✓".green(),
+        format_size(total),
+        orphans.len()
+    );
+
+    Ok(())
+}
+
+/// Recursively collect image files (by extension) under `dir`.
+fn collect_images(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_images(&path, files)?;
+        } else if path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| ICON_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    // rename fails across filesystems — fall back to copy + delete
+    std::fs::copy(from, to)
+        .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+    std::fs::remove_file(from).with_context(|| format!("Failed to delete {}", from.display()))?;
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
@@ -12,6 +13,8 @@ use crate::config::{
 use crate::lockfile::{BadgeLock, Lockfile, PassLock, ProductLock, LOCKFILE_NAME};
 
 pub async fn run(cli: &Cli, from_remote: bool, universe_id: Option<u64>) -> Result<()> {
+    cli.reject_select("init")?;
+
     let config_path = &cli.config;
 
     if !from_remote {
@@ -86,6 +89,8 @@ pub async fn run(cli: &Cli, from_remote: bool, universe_id: Option<u64>) -> Resu
                 for_sale: is_for_sale,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         );
         pass_locks.insert(
@@ -139,6 +144,8 @@ pub async fn run(cli: &Cli, from_remote: bool, universe_id: Option<u64>) -> Resu
                 icon: icon_path,
                 enabled: badge.enabled.unwrap_or(true),
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         );
         badge_locks.insert(
@@ -195,6 +202,9 @@ pub async fn run(cli: &Cli, from_remote: bool, universe_id: Option<u64>) -> Resu
                 regional_pricing: false,
                 store_page,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
+                schedule: Vec::new(),
             },
         );
         product_locks.insert(
@@ -223,6 +233,7 @@ pub async fn run(cli: &Cli, from_remote: bool, universe_id: Option<u64>) -> Resu
         },
         codegen: CodegenConfig::default(),
         icons: icons_config,
+        guardrails: Default::default(),
         passes,
         badges,
         products,
@@ -1,12 +1,37 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
 use anyhow::Result;
 use colored::Colorize;
 
 use crate::api::RbxClient;
 use crate::cli::{Cli, ResourceType};
 use crate::config::Config;
+use crate::lockfile::{Lockfile, LOCKFILE_NAME};
+use crate::select::{Section, Selection};
 
 pub async fn run(cli: &Cli, resource: ResourceType) -> Result<()> {
     let config = Config::load(&cli.config)?;
+    let config_dir = cli.config.parent().unwrap_or(Path::new("."));
+    let lockfile = Lockfile::load(&config_dir.join(LOCKFILE_NAME))?;
+    let selection = Selection::parse_all(&cli.select)?;
+
+    // Remote resources are matched by their local key when the lockfile knows
+    // the ID, and by their remote name otherwise.
+    let section = Section::from(&resource);
+    let keys = match resource {
+        ResourceType::Passes => key_index(&lockfile.passes, |l| l.id),
+        ResourceType::Badges => key_index(&lockfile.badges, |l| l.id),
+        ResourceType::Products => key_index(&lockfile.products, |l| l.id),
+    };
+    let selected = |id: Option<u64>, name: Option<&str>| {
+        let key = id
+            .and_then(|id| keys.get(&id).copied())
+            .or(name)
+            .unwrap_or_default();
+        selection.matches(section, key, config.tags(section, key))
+    };
+
     let client = RbxClient::new(
         cli.api_key.clone(),
         config.experience.universe_id,
@@ -15,7 +40,8 @@ pub async fn run(cli: &Cli, resource: ResourceType) -> Result<()> {
 
     match resource {
         ResourceType::Passes => {
-            let passes = client.list_all_game_passes().await?;
+            let mut passes = client.list_all_game_passes().await?;
+            passes.retain(|p| selected(p.id, p.name.as_deref()));
             println!("{}", "Game Passes".bold());
             println!("{:<12} {:<30} {:<10} Description", "ID", "Name", "Price");
             println!("{}", "-".repeat(70));
@@ -35,9 +61,10 @@ pub async fn run(cli: &Cli, resource: ResourceType) -> Result<()> {
             println!("\nTotal: {}", passes.len());
         }
         ResourceType::Badges => {
-            let badges = client
+            let mut badges = client
                 .list_all_badges(config.experience.universe_id)
                 .await?;
+            badges.retain(|b| selected(b.id, b.name.as_deref()));
             println!("{}", "Badges".bold());
             println!("{:<12} {:<30} {:<10} Description", "ID", "Name", "Enabled");
             println!("{}", "-".repeat(70));
@@ -57,7 +84,8 @@ pub async fn run(cli: &Cli, resource: ResourceType) -> Result<()> {
             println!("\nTotal: {}", badges.len());
         }
         ResourceType::Products => {
-            let products = client.list_all_developer_products().await?;
+            let mut products = client.list_all_developer_products().await?;
+            products.retain(|p| selected(p.id, p.name.as_deref()));
             println!("{}", "Developer Products".bold());
             println!("{:<12} {:<30} {:<10} Description", "ID", "Name", "Price");
             println!("{}", "-".repeat(70));
@@ -80,3 +108,10 @@ pub async fn run(cli: &Cli, resource: ResourceType) -> Result<()> {
 
     Ok(())
 }
+
+fn key_index<L>(locks: &BTreeMap<String, L>, id: impl Fn(&L) -> u64) -> HashMap<u64, &str> {
+    locks
+        .iter()
+        .map(|(key, lock)| (id(lock), key.as_str()))
+        .collect()
+}
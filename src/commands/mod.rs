@@ -1,4 +1,6 @@
 pub mod check;
+pub mod codegen;
+pub mod icons;
 pub mod init;
 pub mod list;
 pub mod pull;
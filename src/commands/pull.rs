@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
@@ -6,8 +6,10 @@ use colored::Colorize;
 
 use crate::api::RbxClient;
 use crate::cli::Cli;
-use crate::config::{BadgeConfig, Config, PassConfig, ProductConfig};
+use crate::config::{BadgeConfig, Config, IconsConfig, PassConfig, ProductConfig};
 use crate::lockfile::{BadgeLock, Lockfile, PassLock, ProductLock, LOCKFILE_NAME};
+use crate::schedule;
+use crate::select::{Section, Selection, Selector};
 
 struct IconConflict {
     resource_type: &'static str,
@@ -30,7 +32,15 @@ struct ConfigChange {
     field_changes: Vec<String>,
 }
 
-pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bool) -> Result<()> {
+pub async fn run(
+    cli: &Cli,
+    selectors: &[String],
+    dry_run: bool,
+    accept_remote: bool,
+    accept_local: bool,
+) -> Result<()> {
+    let selectors = Selector::parse_all(selectors)?;
+    let selection = Selection::parse_all(&cli.select)?.with_selectors(&selectors);
     let mut config = Config::load(&cli.config)?;
     let config_dir = cli.config.parent().unwrap_or(Path::new("."));
     let lockfile_path = config_dir.join(LOCKFILE_NAME);
@@ -205,12 +215,56 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
         );
     }
 
+    for selector in &selectors {
+        let matched = match selector.section {
+            Section::Passes => pass_locks
+                .keys()
+                .any(|k| selector.matches(Section::Passes, k)),
+            Section::Badges => badge_locks
+                .keys()
+                .any(|k| selector.matches(Section::Badges, k)),
+            Section::Products => product_locks
+                .keys()
+                .any(|k| selector.matches(Section::Products, k)),
+            Section::Extra => false,
+        };
+        if !matched {
+            println!(
+                "{} '{}' doesn't match any remote resource",
+                "!".yellow(),
+                selector
+            );
+        }
+    }
+
+    // With --select or selectors, unselected resources keep their old lock
+    // entries and config, so only the chosen ones are refreshed.
+    let selected = |config: &Config, section: Section, key: &str| {
+        selection.matches(section, key, config.tags(section, key))
+    };
+    keep_unselected(&mut pass_locks, &old_lockfile.passes, |key| {
+        selected(&config, Section::Passes, key)
+    });
+    keep_unselected(&mut badge_locks, &old_lockfile.badges, |key| {
+        selected(&config, Section::Badges, key)
+    });
+    keep_unselected(&mut product_locks, &old_lockfile.products, |key| {
+        selected(&config, Section::Products, key)
+    });
+    let selected_passes = only_selected(&pass_locks, |key| selected(&config, Section::Passes, key));
+    let selected_badges =
+        only_selected(&badge_locks, |key| selected(&config, Section::Badges, key));
+    let selected_products = only_selected(&product_locks, |key| {
+        selected(&config, Section::Products, key)
+    });
+
     // -----------------------------------------------------------------------
     // Update config from remote state
     // -----------------------------------------------------------------------
-    let pass_config_changes = update_pass_config(&mut config, &pass_locks);
-    let badge_config_changes = update_badge_config(&mut config, &badge_locks);
-    let product_config_changes = update_product_config(&mut config, &product_locks);
+    let pass_config_changes = update_pass_config(&mut config, &selected_passes);
+    let badge_config_changes = update_badge_config(&mut config, &selected_badges);
+    let product_config_changes =
+        update_product_config(&mut config, &selected_products, schedule::now())?;
 
     // -----------------------------------------------------------------------
     // Dry run — show diff and exit
@@ -245,11 +299,14 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
 
     // Check passes
     for (name, new_lock) in &mut pass_locks {
+        if !selected_passes.contains_key(name) {
+            continue;
+        }
         let old_icon_id = old_lockfile
             .passes
             .get(name)
             .and_then(|l| l.icon_asset_id.as_ref());
-        let local_icon = config.passes.get(name).and_then(|c| c.icon.as_ref());
+        let local_icon = config.pass_icon(name, config_dir);
 
         match resolve_icon(
             "pass",
@@ -257,9 +314,9 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
             new_lock.id,
             old_icon_id,
             &new_lock.icon_asset_id,
-            local_icon,
+            local_icon.as_ref(),
             config_dir,
-            &config.icons.dir,
+            &config.icons,
             accept_remote,
             accept_local,
             &mut conflicts,
@@ -278,11 +335,14 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
 
     // Check badges
     for (name, new_lock) in &mut badge_locks {
+        if !selected_badges.contains_key(name) {
+            continue;
+        }
         let old_icon_id = old_lockfile
             .badges
             .get(name)
             .and_then(|l| l.icon_asset_id.as_ref());
-        let local_icon = config.badges.get(name).and_then(|c| c.icon.as_ref());
+        let local_icon = config.badge_icon(name, config_dir);
 
         match resolve_icon(
             "badge",
@@ -290,9 +350,9 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
             new_lock.id,
             old_icon_id,
             &new_lock.icon_asset_id,
-            local_icon,
+            local_icon.as_ref(),
             config_dir,
-            &config.icons.dir,
+            &config.icons,
             accept_remote,
             accept_local,
             &mut conflicts,
@@ -311,11 +371,14 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
 
     // Check products
     for (name, new_lock) in &mut product_locks {
+        if !selected_products.contains_key(name) {
+            continue;
+        }
         let old_icon_id = old_lockfile
             .products
             .get(name)
             .and_then(|l| l.icon_asset_id.as_ref());
-        let local_icon = config.products.get(name).and_then(|c| c.icon.as_ref());
+        let local_icon = config.product_icon(name, config_dir);
 
         match resolve_icon(
             "product",
@@ -323,9 +386,9 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
             new_lock.id,
             old_icon_id,
             &new_lock.icon_asset_id,
-            local_icon,
+            local_icon.as_ref(),
             config_dir,
-            &config.icons.dir,
+            &config.icons,
             accept_remote,
             accept_local,
             &mut conflicts,
@@ -382,7 +445,9 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
         std::fs::write(&dl.save_path, &bytes)?;
         let hash = hash_bytes(&bytes);
 
-        // Compute path relative to config dir for config icon field
+        // Compute path relative to config dir for config icon field.
+        // With icons.auto the download already sits at the convention path.
+        let set_icon = !config.icons.auto;
         let relative_icon = dl
             .save_path
             .strip_prefix(config_dir)
@@ -395,7 +460,7 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
                     lock.icon_hash = Some(hash);
                 }
                 if let Some(pc) = config.passes.get_mut(&dl.name) {
-                    if pc.icon.is_none() {
+                    if set_icon && pc.icon.is_none() {
                         pc.icon = Some(relative_icon.clone());
                     }
                 }
@@ -405,7 +470,7 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
                     lock.icon_hash = Some(hash);
                 }
                 if let Some(bc) = config.badges.get_mut(&dl.name) {
-                    if bc.icon.is_none() {
+                    if set_icon && bc.icon.is_none() {
                         bc.icon = Some(relative_icon.clone());
                     }
                 }
@@ -415,7 +480,7 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
                     lock.icon_hash = Some(hash);
                 }
                 if let Some(pc) = config.products.get_mut(&dl.name) {
-                    if pc.icon.is_none() {
+                    if set_icon && pc.icon.is_none() {
                         pc.icon = Some(relative_icon.clone());
                     }
                 }
@@ -448,6 +513,36 @@ pub async fn run(cli: &Cli, dry_run: bool, accept_remote: bool, accept_local: bo
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Selection helpers
+// ---------------------------------------------------------------------------
+
+/// Replace every unselected entry in `remote` with its old lock entry, or drop
+/// it if there was none.
+fn keep_unselected<L: Clone>(
+    remote: &mut BTreeMap<String, L>,
+    old: &BTreeMap<String, L>,
+    selected: impl Fn(&str) -> bool,
+) {
+    remote.retain(|key, _| selected(key) || old.contains_key(key));
+    for (key, lock) in old {
+        if !selected(key) {
+            remote.insert(key.clone(), lock.clone());
+        }
+    }
+}
+
+fn only_selected<L: Clone>(
+    locks: &BTreeMap<String, L>,
+    selected: impl Fn(&str) -> bool,
+) -> BTreeMap<String, L> {
+    locks
+        .iter()
+        .filter(|(key, _)| selected(key))
+        .map(|(key, lock)| (key.clone(), lock.clone()))
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Config update helpers
 // ---------------------------------------------------------------------------
@@ -510,6 +605,8 @@ fn update_pass_config(
                     for_sale: lock.for_sale,
                     regional_pricing: false,
                     path: None,
+                    tags: Vec::new(),
+                    data: BTreeMap::new(),
                 },
             );
             changes.push(ConfigChange {
@@ -564,6 +661,8 @@ fn update_badge_config(
                     icon: None,
                     enabled: lock.enabled,
                     path: None,
+                    tags: Vec::new(),
+                    data: BTreeMap::new(),
                 },
             );
             changes.push(ConfigChange {
@@ -577,21 +676,25 @@ fn update_badge_config(
     changes
 }
 
+/// Products with an active schedule window are compared against the
+/// scheduled values, so a running sale isn't written back as the base price.
 fn update_product_config(
     config: &mut Config,
     product_locks: &std::collections::BTreeMap<String, ProductLock>,
-) -> Vec<ConfigChange> {
+    now: i64,
+) -> Result<Vec<ConfigChange>> {
     let mut changes = Vec::new();
 
     for (key, lock) in product_locks {
         let new_name = config_name(&lock.name, key);
         if let Some(pc) = config.products.get_mut(key) {
+            let (effective_price, effective_for_sale) = pc.effective_at(now)?;
             let mut field_changes = Vec::new();
             if pc.name != new_name {
                 field_changes.push(format!("name: {:?} -> {:?}", pc.name, new_name));
                 pc.name = new_name;
             }
-            if pc.price != lock.price {
+            if pc.price != lock.price && effective_price != lock.price {
                 field_changes.push(format!("price: {} -> {}", pc.price, lock.price));
                 pc.price = lock.price;
             }
@@ -602,7 +705,7 @@ fn update_product_config(
                 ));
                 pc.description = lock.description.clone();
             }
-            if pc.for_sale != lock.for_sale {
+            if pc.for_sale != lock.for_sale && effective_for_sale != lock.for_sale {
                 field_changes.push(format!("for_sale: {} -> {}", pc.for_sale, lock.for_sale));
                 pc.for_sale = lock.for_sale;
             }
@@ -632,6 +735,9 @@ fn update_product_config(
                     regional_pricing: false,
                     store_page: lock.store_page,
                     path: None,
+                    tags: Vec::new(),
+                    data: BTreeMap::new(),
+                    schedule: Vec::new(),
                 },
             );
             changes.push(ConfigChange {
@@ -642,7 +748,7 @@ fn update_product_config(
         }
     }
 
-    changes
+    Ok(changes)
 }
 
 fn print_config_changes(resource_type: &str, changes: &[ConfigChange]) -> bool {
@@ -901,9 +1007,9 @@ fn resolve_icon(
     resource_id: u64,
     old_icon_id: Option<&u64>,
     new_icon_id: &Option<u64>,
-    local_icon: Option<&std::path::PathBuf>,
+    local_icon: Option<&PathBuf>,
     config_dir: &Path,
-    icon_dir: &Path,
+    icons: &IconsConfig,
     accept_remote: bool,
     accept_local: bool,
     conflicts: &mut Vec<IconConflict>,
@@ -925,10 +1031,17 @@ fn resolve_icon(
         if let Some(&asset_id) = new_icon_id.as_ref() {
             let save_path = if let Some(local_path) = local_icon {
                 config_dir.join(local_path)
+            } else if icons.auto {
+                let section = match resource_type {
+                    "pass" => "passes",
+                    "badge" => "badges",
+                    _ => "products",
+                };
+                config_dir.join(icons.convention_path(section, name, "png"))
             } else {
                 config_dir.join(format!(
                     "{}/{}-{}-{}.png",
-                    icon_dir.display(),
+                    icons.dir.display(),
                     resource_type,
                     resource_id,
                     name
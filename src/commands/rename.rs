@@ -7,6 +7,8 @@ use crate::config::Config;
 use crate::lockfile::{Lockfile, LOCKFILE_NAME};
 
 pub fn run(cli: &Cli, resource: ResourceType, old_key: &str, new_key: &str) -> Result<()> {
+    cli.reject_select("rename")?;
+
     let config_path = &cli.config;
     let lockfile_path = config_path
         .parent()
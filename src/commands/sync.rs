@@ -1,4 +1,6 @@
-use std::path::Path;
+use std::collections::HashSet;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use colored::Colorize;
@@ -7,42 +9,71 @@ use crate::api::RbxClient;
 use crate::cli::{Cli, ResourceType};
 use crate::codegen;
 use crate::config::{resolve_name, Config};
-use crate::diff::{build_sync_plan, Action};
+use crate::diff::{build_sync_plan, Action, ResourceAction, SyncPlan};
+use crate::guardrails;
 use crate::lockfile::{BadgeLock, Lockfile, PassLock, ProductLock};
+use crate::schedule;
+use crate::select::{Section, Selection, Selector};
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     cli: &Cli,
+    selectors: &[String],
     dry_run: bool,
     only: Option<Vec<ResourceType>>,
-    badge_cost: u64,
+    at: Option<&str>,
+    allow_price_change: bool,
+    badge_cost: Option<u64>,
+    yes: bool,
 ) -> Result<()> {
-    let config = Config::load(&cli.config)?;
+    let mut config = Config::load(&cli.config)?;
+    let at = at.map(schedule::parse_time).transpose()?;
+    schedule::apply(&mut config, at.unwrap_or_else(schedule::now))?;
     let config_dir = cli.config.parent().unwrap_or(Path::new("."));
     let lockfile_path = config_dir.join(crate::lockfile::LOCKFILE_NAME);
     let mut lockfile = Lockfile::load(&lockfile_path)?;
     lockfile.universe_id = config.experience.universe_id;
     lockfile.version = 1;
 
-    let plan = build_sync_plan(&config, &lockfile, config_dir)?;
+    let selectors = Selector::parse_all(selectors)?;
+    let selection = Selection::parse_all(&cli.select)?.with_selectors(&selectors);
+    let mut plan = build_sync_plan(&config, &lockfile, config_dir)?;
+
+    for selector in &selectors {
+        let sections = [
+            (Section::Passes, &plan.passes),
+            (Section::Badges, &plan.badges),
+            (Section::Products, &plan.products),
+        ];
+        let matched = sections
+            .iter()
+            .any(|(section, actions)| actions.iter().any(|a| selector.matches(*section, &a.name)));
+        if !matched {
+            println!(
+                "{} '{}' doesn't match any resource in the config",
+                "!".yellow(),
+                selector
+            );
+        }
+    }
+    plan.retain(|section, key| {
+        only.as_ref()
+            .is_none_or(|types| types.iter().any(|t| Section::from(t) == section))
+            && selection.matches(section, key, config.tags(section, key))
+    });
 
     for warning in &plan.warnings {
         println!("{} {}", "!".yellow(), warning);
     }
+    for warning in codegen::rojo_warnings(&config, config_dir)? {
+        println!("{} {}", "!".yellow(), warning);
+    }
 
     if !plan.has_changes() {
         println!("{} Everything is up to date.", "✓".green());
 
-        if let Some(output) = &config.codegen.output {
-            let output_path = config_dir.join(output);
-            let tree = codegen::build_tree(&lockfile, &config);
-            codegen::generate_luau(&tree, &output_path)?;
-            println!("{} Generated {}", "✓".green(), output_path.display());
-
-            if config.codegen.typescript {
-                let ts_path = output_path.with_extension("d.ts");
-                codegen::generate_typescript(&tree, &ts_path)?;
-                println!("{} Generated {}", "✓".green(), ts_path.display());
-            }
+        for path in codegen::generate(&lockfile, &config, config_dir)? {
+            println!("{} Generated {}", "✓".green(), path.display());
         }
 
         return Ok(());
@@ -69,16 +100,93 @@ pub async fn run(
 
     println!("\n{}", plan.summary());
 
+    let mut client = RbxClient::new(
+        cli.api_key.clone(),
+        config.experience.universe_id,
+        config.icons.bleed,
+    );
+
+    let violations = guardrails::price_violations(&plan, &config, &lockfile);
+    if !violations.is_empty() {
+        println!();
+    }
+    for violation in &violations {
+        if violation.overridable && allow_price_change {
+            println!("{} {} (allowed)", "!".yellow(), violation);
+        } else {
+            println!("{} {}", "✗".red(), violation);
+        }
+    }
+
     if dry_run {
+        // Without an API key the quota lookup can only fail, so a dry run
+        // estimates instead of trying.
+        let fetch_quota = cli.api_key.is_some();
+        let costs = expected_badge_costs(&client, &config, &plan, badge_cost, fetch_quota).await;
+        if !costs.is_empty() {
+            let spend = costs.iter().sum();
+            println!("Projected spend: R${spend} for {} badge(s)", costs.len());
+            if let Err(err) = guardrails::check_badge_budget(&config, spend) {
+                println!("{} {:#}", "✗".red(), err);
+            }
+        }
         println!("\n{} Dry run — no changes applied.", "ℹ".blue());
         return Ok(());
     }
 
-    let client = RbxClient::new(
-        cli.api_key.clone(),
-        config.experience.universe_id,
-        config.icons.bleed,
-    );
+    if !yes && std::io::stdin().is_terminal() && std::io::stdout().is_terminal() {
+        println!();
+        let mut stdin = std::io::stdin().lock();
+        if !confirm_actions(&mut plan, &mut stdin, &mut std::io::stdout())? {
+            println!("{} Aborted — nothing was synced.", "✗".red());
+            return Ok(());
+        }
+        if !plan.has_changes() {
+            println!("{} Nothing to apply.", "ℹ".blue());
+            return Ok(());
+        }
+    }
+
+    // Price guardrails apply to the confirmed plan, so declining a blocked
+    // change lets the rest through. They're checked before any API call, so
+    // a blocked sync changes nothing.
+    guardrails::check_price_changes(&plan, &config, &lockfile, allow_price_change)?;
+
+    // The budget covers the creates left after confirmation, and is checked
+    // before any of them run.
+    let badge_costs = expected_badge_costs(&client, &config, &plan, badge_cost, true).await;
+    if !badge_costs.is_empty() {
+        let spend = badge_costs.iter().sum();
+        println!(
+            "Projected spend: R${spend} for {} badge(s)",
+            badge_costs.len()
+        );
+        guardrails::check_badge_budget(&config, spend)?;
+    }
+    let mut badge_costs = badge_costs.into_iter();
+
+    // Process every icon that will be uploaded up front, in parallel
+    let mut upload_icons = Vec::new();
+    if should_sync(&ResourceType::Passes) {
+        upload_icons.extend(icons_to_upload(&plan.passes, |key| {
+            config.pass_icon(key, config_dir)
+        }));
+    }
+    if should_sync(&ResourceType::Badges) {
+        upload_icons.extend(icons_to_upload(&plan.badges, |key| {
+            config.badge_icon(key, config_dir)
+        }));
+    }
+    if should_sync(&ResourceType::Products) {
+        upload_icons.extend(icons_to_upload(&plan.products, |key| {
+            config.product_icon(key, config_dir)
+        }));
+    }
+    if !upload_icons.is_empty() {
+        println!("Processing {} icon(s)...", upload_icons.len());
+        let paths = upload_icons.iter().map(|p| config_dir.join(p)).collect();
+        client.preload_icons(paths)?;
+    }
 
     // Sync passes
     if should_sync(&ResourceType::Passes) {
@@ -87,7 +195,9 @@ pub async fn run(
                 Action::Create => {
                     let pass_cfg = &config.passes[&action.name];
                     let display_name = resolve_name(pass_cfg.name.as_deref(), &action.name);
-                    let icon_path = pass_cfg.icon.as_ref().map(|p| config_dir.join(p));
+                    let icon_path = config
+                        .pass_icon(&action.name, config_dir)
+                        .map(|p| config_dir.join(p));
                     let icon_hash = icon_path.as_ref().map(|p| hash_file(p)).transpose()?;
 
                     print!("  Creating pass '{}'...", action.name);
@@ -124,7 +234,9 @@ pub async fn run(
                     let pass_cfg = &config.passes[&action.name];
                     let display_name = resolve_name(pass_cfg.name.as_deref(), &action.name);
                     let lock = &lockfile.passes[&action.name];
-                    let icon_path = pass_cfg.icon.as_ref().map(|p| config_dir.join(p));
+                    let icon_path = config
+                        .pass_icon(&action.name, config_dir)
+                        .map(|p| config_dir.join(p));
                     let icon_hash = icon_path.as_ref().map(|p| hash_file(p)).transpose()?;
 
                     let icon_changed = match (&icon_hash, &lock.icon_hash) {
@@ -184,7 +296,9 @@ pub async fn run(
                 Action::Create => {
                     let badge_cfg = &config.badges[&action.name];
                     let display_name = resolve_name(badge_cfg.name.as_deref(), &action.name);
-                    let icon_path = badge_cfg.icon.as_ref().map(|p| config_dir.join(p));
+                    let icon_path = config
+                        .badge_icon(&action.name, config_dir)
+                        .map(|p| config_dir.join(p));
                     let icon_hash = icon_path.as_ref().map(|p| hash_file(p)).transpose()?;
 
                     print!("  Creating badge '{}'...", action.name);
@@ -194,7 +308,7 @@ pub async fn run(
                             badge_cfg.description.as_deref(),
                             icon_path.as_deref(),
                             payment_source,
-                            badge_cost,
+                            badge_costs.next().unwrap_or(0),
                         )
                         .await?;
 
@@ -239,7 +353,7 @@ pub async fn run(
                     let mut new_icon_hash = lock.icon_hash.clone();
 
                     if icon_changed {
-                        if let Some(icon) = &badge_cfg.icon {
+                        if let Some(icon) = config.badge_icon(&action.name, config_dir) {
                             let icon_path = config_dir.join(icon);
                             print!("  Updating badge '{}' icon...", action.name);
                             let icon_result = client.update_badge_icon(lock.id, &icon_path).await?;
@@ -289,7 +403,9 @@ pub async fn run(
                 Action::Create => {
                     let product_cfg = &config.products[&action.name];
                     let display_name = resolve_name(product_cfg.name.as_deref(), &action.name);
-                    let icon_path = product_cfg.icon.as_ref().map(|p| config_dir.join(p));
+                    let icon_path = config
+                        .product_icon(&action.name, config_dir)
+                        .map(|p| config_dir.join(p));
                     let icon_hash = icon_path.as_ref().map(|p| hash_file(p)).transpose()?;
 
                     print!("  Creating product '{}'...", action.name);
@@ -327,7 +443,9 @@ pub async fn run(
                     let product_cfg = &config.products[&action.name];
                     let display_name = resolve_name(product_cfg.name.as_deref(), &action.name);
                     let lock = &lockfile.products[&action.name];
-                    let icon_path = product_cfg.icon.as_ref().map(|p| config_dir.join(p));
+                    let icon_path = config
+                        .product_icon(&action.name, config_dir)
+                        .map(|p| config_dir.join(p));
                     let icon_hash = icon_path.as_ref().map(|p| hash_file(p)).transpose()?;
 
                     let icon_changed = match (&icon_hash, &lock.icon_hash) {
@@ -379,57 +497,173 @@ pub async fn run(
 
     println!("{} Sync complete.", "✓".green());
 
-    if let Some(output) = &config.codegen.output {
-        let output_path = config_dir.join(output);
-        let tree = codegen::build_tree(&lockfile, &config);
-        codegen::generate_luau(&tree, &output_path)?;
-        println!("{} Generated {}", "✓".green(), output_path.display());
+    for path in codegen::generate(&lockfile, &config, config_dir)? {
+        println!("{} Generated {}", "✓".green(), path.display());
+    }
 
-        if config.codegen.typescript {
-            let ts_path = output_path.with_extension("d.ts");
-            codegen::generate_typescript(&tree, &ts_path)?;
-            println!("{} Generated {}", "✓".green(), ts_path.display());
+    Ok(())
+}
+
+/// Ask whether to apply each create and update in `plan`, dropping the ones
+/// the user skips so their lockfile entries stay as they are. Returns `false`
+/// if the user aborts; end of input counts as aborting.
+pub fn confirm_actions(
+    plan: &mut SyncPlan,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<bool> {
+    let sections = [
+        (Section::Passes, "pass", &plan.passes),
+        (Section::Badges, "badge", &plan.badges),
+        (Section::Products, "product", &plan.products),
+    ];
+    let mut skipped = HashSet::new();
+
+    for (section, resource_type, actions) in sections {
+        for action in actions {
+            if matches!(action.action, Action::Skip) {
+                continue;
+            }
+            write_action(output, resource_type, action)?;
+            loop {
+                write!(output, "  Apply? [y]es / [s]kip / [a]bort: ")?;
+                output.flush()?;
+                let mut answer = String::new();
+                if input.read_line(&mut answer)? == 0 {
+                    writeln!(output)?;
+                    return Ok(false);
+                }
+                match answer.trim().to_lowercase().as_str() {
+                    "y" | "yes" => break,
+                    "s" | "skip" | "n" | "no" => {
+                        skipped.insert((section, action.name.clone()));
+                        break;
+                    }
+                    "a" | "abort" | "q" | "quit" => return Ok(false),
+                    _ => {}
+                }
+            }
         }
     }
 
-    Ok(())
+    plan.retain(|section, key| !skipped.contains(&(section, key.to_string())));
+    Ok(true)
 }
 
-fn print_action(resource_type: &str, action: &crate::diff::ResourceAction) {
+fn print_action(resource_type: &str, action: &ResourceAction) {
+    // Failing to write to stdout isn't worth aborting a sync over.
+    let _ = write_action(&mut std::io::stdout(), resource_type, action);
+}
+
+fn write_action(
+    out: &mut impl Write,
+    resource_type: &str,
+    action: &ResourceAction,
+) -> std::io::Result<()> {
     match &action.action {
         Action::Create => {
-            println!(
+            writeln!(
+                out,
                 "  {} {} {} {}",
                 "+".green(),
                 "create".green(),
                 resource_type,
                 action.name.bold()
-            );
+            )
         }
         Action::Update { changes } => {
-            println!(
+            writeln!(
+                out,
                 "  {} {} {} {}",
                 "~".yellow(),
                 "update".yellow(),
                 resource_type,
                 action.name.bold()
-            );
+            )?;
             for change in changes {
-                println!("    {} {}", "·".dimmed(), change);
+                writeln!(out, "    {} {}", "·".dimmed(), change)?;
             }
+            Ok(())
         }
         Action::Skip => {
-            println!(
+            writeln!(
+                out,
                 "  {} {} {} {}",
                 "=".dimmed(),
                 "skip".dimmed(),
                 resource_type,
                 action.name.dimmed()
+            )
+        }
+    }
+}
+
+fn count_creates(actions: &[ResourceAction]) -> usize {
+    actions
+        .iter()
+        .filter(|a| matches!(a.action, Action::Create))
+        .count()
+}
+
+/// The expected cost of each badge `plan` creates, in order. Badges are free
+/// until the universe's daily quota runs out, so the quota is fetched unless
+/// `--badge-cost` fixes the cost or `fetch_quota` is false. If it isn't
+/// fetched, every badge is assumed to cost the full price.
+async fn expected_badge_costs(
+    client: &RbxClient,
+    config: &Config,
+    plan: &SyncPlan,
+    badge_cost: Option<u64>,
+    fetch_quota: bool,
+) -> Vec<u64> {
+    let creates = count_creates(&plan.badges);
+    if creates == 0 {
+        return Vec::new();
+    }
+    if let Some(cost) = badge_cost {
+        return vec![cost; creates];
+    }
+
+    let cost = guardrails::badge_cost(config);
+    if !fetch_quota {
+        println!(
+            "{} Free badge quota unavailable without an API key, assuming R${} per badge (set --badge-cost to override)",
+            "ℹ".blue(),
+            cost
+        );
+        return vec![cost; creates];
+    }
+    match client.get_free_badges_quota().await {
+        Ok(quota) => guardrails::badge_costs(creates, quota, cost),
+        Err(err) => {
+            println!(
+                "{} Couldn't fetch the free badge quota, assuming R${} per badge (set --badge-cost to override): {:#}",
+                "!".yellow(),
+                cost,
+                err
             );
+            vec![cost; creates]
         }
     }
 }
 
+/// Icons that will be uploaded when `actions` are applied: every create with an
+/// icon, and every update whose icon changed.
+fn icons_to_upload(
+    actions: &[ResourceAction],
+    icon_for: impl Fn(&str) -> Option<PathBuf>,
+) -> Vec<PathBuf> {
+    actions
+        .iter()
+        .filter(|a| match &a.action {
+            Action::Create => true,
+            Action::Update { changes } => changes.iter().any(|c| c.field == "icon"),
+            Action::Skip => false,
+        })
+        .filter_map(|a| icon_for(&a.name))
+        .collect()
+}
+
 fn hash_file(path: &Path) -> Result<String> {
     let bytes = std::fs::read(path)?;
     Ok(blake3::hash(&bytes).to_hex().to_string())
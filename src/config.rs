@@ -4,6 +4,8 @@ use std::path::{Path, PathBuf};
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::select::{Section, Selection};
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Config {
     pub experience: Experience,
@@ -14,6 +16,9 @@ pub struct Config {
     #[serde(default, skip_serializing_if = "IconsConfig::is_default")]
     pub icons: IconsConfig,
 
+    #[serde(default, skip_serializing_if = "GuardrailsConfig::is_default")]
+    pub guardrails: GuardrailsConfig,
+
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub passes: BTreeMap<String, PassConfig>,
 
@@ -83,15 +88,65 @@ impl CodegenPaths {
     }
 }
 
+/// What the generated `byId` reverse lookup table maps IDs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReverseLookup {
+    /// The resource's config key, e.g. `"VIP"`
+    Key,
+    /// The entry's dotted path in the generated table, e.g. `"passes.VIP"`
+    Path,
+}
+
+/// Naming convention applied to resource keys in generated code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyCase {
+    /// Keys as written in the config, e.g. `vip_pass`
+    #[default]
+    Preserve,
+    /// `vipPass`
+    Camel,
+    /// `VipPass`
+    Pascal,
+    /// `vip_pass`
+    Snake,
+    /// `VIP_PASS`
+    ScreamingSnake,
+}
+
+impl KeyCase {
+    fn is_default(&self) -> bool {
+        matches!(self, KeyCase::Preserve)
+    }
+}
+
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct CodegenConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output: Option<PathBuf>,
 
+    /// Derive `output` from a ModuleScript path in the Rojo project,
+    /// e.g. `ReplicatedStorage.Shared.GameIds`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_instance: Option<String>,
+
+    /// Rojo project to check outputs against (default: `default.project.json` if present)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rojo_project: Option<PathBuf>,
+
     /// Also generate a TypeScript definition file (.d.ts)
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub typescript: bool,
 
+    /// Where to write the .d.ts file (default: `output` with a .d.ts extension)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typescript_output: Option<PathBuf>,
+
+    /// Directory to generate a roblox-ts package in (`init.luau` + `index.d.ts`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roblox_ts: Option<PathBuf>,
+
     /// Code generation style: "flat" (default) or "nested"
     #[serde(default, skip_serializing_if = "CodegenStyle::is_default")]
     pub style: CodegenStyle,
@@ -99,18 +154,167 @@ pub struct CodegenConfig {
     #[serde(default, skip_serializing_if = "CodegenPaths::is_default")]
     pub paths: CodegenPaths,
 
+    /// Naming convention for resource keys: "preserve" (default), "camel",
+    /// "pascal", "snake" or "screaming_snake"
+    #[serde(default, skip_serializing_if = "KeyCase::is_default")]
+    pub key_case: KeyCase,
+
     /// Extra entries injected into the generated file: `"path.to.key" = asset_id`
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub extra: BTreeMap<String, u64>,
+
+    /// Emit each entry as a record (id, name, price, ...) instead of a bare ID
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub records: bool,
+
+    /// Also emit a `byId` table mapping IDs back to keys or paths
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reverse: Option<ReverseLookup>,
+
+    /// Emit `--!strict`, exported key/table types and frozen tables in the Luau module
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict: bool,
+
+    /// Path to write a `.rbxm`/`.rbxmx` model containing the Luau module as a ModuleScript
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<PathBuf>,
+
+    /// Path to generate a typed `MarketplaceService` wrapper module (omit to disable)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marketplace: Option<PathBuf>,
+
+    /// Path to write a JSON (or `.yaml`/`.yml`) manifest of all resources (omit to disable)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<PathBuf>,
+
+    /// Additional outputs, each with its own path, language, layout and filters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<CodegenOutput>,
+
+    /// User-defined templates rendered alongside the built-in outputs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<CodegenTemplate>,
+}
+
+/// Output format of a `[[codegen.outputs]]` entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CodegenLanguage {
+    /// A Luau module
+    #[default]
+    Luau,
+    /// A TypeScript definition file (.d.ts)
+    Typescript,
+    /// A roblox-ts package directory (`init.luau` + `index.d.ts`)
+    RobloxTs,
+    /// A Roblox model (`.rbxm` or `.rbxmx`) containing the Luau module
+    Model,
+}
+
+/// An `include`/`exclude` filter: a resource type or `tag:<name>`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum CodegenFilter {
+    Passes,
+    Badges,
+    Products,
+    Extra,
+    Tag(String),
+}
+
+impl TryFrom<String> for CodegenFilter {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.as_str() {
+            "passes" => Ok(CodegenFilter::Passes),
+            "badges" => Ok(CodegenFilter::Badges),
+            "products" => Ok(CodegenFilter::Products),
+            "extra" => Ok(CodegenFilter::Extra),
+            _ => match s.strip_prefix("tag:") {
+                Some(tag) if !tag.is_empty() => Ok(CodegenFilter::Tag(tag.to_string())),
+                _ => Err(format!(
+                    "invalid filter '{s}': expected \"passes\", \"badges\", \"products\", \"extra\" or \"tag:<name>\""
+                )),
+            },
+        }
+    }
+}
+
+impl From<CodegenFilter> for String {
+    fn from(filter: CodegenFilter) -> Self {
+        match filter {
+            CodegenFilter::Passes => "passes".into(),
+            CodegenFilter::Badges => "badges".into(),
+            CodegenFilter::Products => "products".into(),
+            CodegenFilter::Extra => "extra".into(),
+            CodegenFilter::Tag(tag) => format!("tag:{tag}"),
+        }
+    }
+}
+
+/// An additional generated file with its own layout and filters.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CodegenOutput {
+    /// File to write (a directory for `roblox-ts`), relative to the config file
+    pub path: PathBuf,
+
+    #[serde(default)]
+    pub language: CodegenLanguage,
+
+    /// Overrides `codegen.style` for this output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<CodegenStyle>,
+
+    /// Overrides `codegen.paths` per section for this output
+    #[serde(default, skip_serializing_if = "CodegenPaths::is_default")]
+    pub paths: CodegenPaths,
+
+    /// Overrides `codegen.key_case` for this output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_case: Option<KeyCase>,
+
+    /// Only keep entries matching one of these filters (default: everything)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<CodegenFilter>,
+
+    /// Drop entries matching any of these filters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<CodegenFilter>,
+
+    /// A selection expression (same syntax as `--select`) entries must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub select: Option<Selection>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CodegenTemplate {
+    /// Template file (MiniJinja syntax), relative to the config file
+    pub template: PathBuf,
+    /// Where to write the rendered output, relative to the config file
+    pub output: PathBuf,
 }
 
 impl CodegenConfig {
     fn is_default(&self) -> bool {
         self.output.is_none()
+            && self.output_instance.is_none()
+            && self.rojo_project.is_none()
             && !self.typescript
+            && self.typescript_output.is_none()
+            && self.roblox_ts.is_none()
+            && self.model.is_none()
             && self.style.is_default()
             && self.paths.is_default()
+            && self.key_case.is_default()
             && self.extra.is_empty()
+            && !self.records
+            && self.reverse.is_none()
+            && !self.strict
+            && self.marketplace.is_none()
+            && self.manifest.is_none()
+            && self.templates.is_empty()
+            && self.outputs.is_empty()
     }
 }
 
@@ -123,6 +327,10 @@ pub struct IconsConfig {
     /// Directory for downloaded icons (default: "icons")
     #[serde(default = "default_icon_dir")]
     pub dir: PathBuf,
+
+    /// Discover icons at `<dir>/<section>/<key>.<ext>` when no explicit path is set
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto: bool,
 }
 
 impl Default for IconsConfig {
@@ -130,13 +338,63 @@ impl Default for IconsConfig {
         Self {
             bleed: true,
             dir: default_icon_dir(),
+            auto: false,
         }
     }
 }
 
+/// Image extensions tried, in order, when discovering icons by convention.
+pub const ICON_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "tga", "webp"];
+
 impl IconsConfig {
     fn is_default(&self) -> bool {
-        self.bleed && self.dir == default_icon_dir()
+        self.bleed && self.dir == default_icon_dir() && !self.auto
+    }
+
+    /// Conventional icon path (relative to the config dir) for a resource,
+    /// e.g. `icons/passes/VIP.png`.
+    pub fn convention_path(&self, section: &str, key: &str, extension: &str) -> PathBuf {
+        self.dir.join(section).join(format!("{key}.{extension}"))
+    }
+
+    /// Find an existing icon for a resource by convention, trying each of
+    /// [`ICON_EXTENSIONS`] in order.
+    pub fn discover(&self, config_dir: &Path, section: &str, key: &str) -> Option<PathBuf> {
+        ICON_EXTENSIONS
+            .iter()
+            .map(|ext| self.convention_path(section, key, ext))
+            .find(|path| config_dir.join(path).is_file())
+    }
+}
+
+/// Limits that `sync` checks before making any API call: price changes that
+/// need `--allow-price-change`, and a budget for badge creation.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct GuardrailsConfig {
+    /// Largest allowed price change, as a percentage of the old price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_price_change_percent: Option<u64>,
+    /// Largest allowed price change in Robux
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_price_change: Option<u64>,
+    /// Refuse to raise the price of a game pass
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub forbid_pass_price_increase: bool,
+    /// Most Robux a single sync may spend creating badges
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_badge_spend: Option<u64>,
+    /// Robux charged per badge once the free quota is used, if not the default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub badge_cost: Option<u64>,
+}
+
+impl GuardrailsConfig {
+    fn is_default(&self) -> bool {
+        self.max_price_change_percent.is_none()
+            && self.max_price_change.is_none()
+            && !self.forbid_pass_price_increase
+            && self.max_badge_spend.is_none()
+            && self.badge_cost.is_none()
     }
 }
 
@@ -156,6 +414,12 @@ pub struct PassConfig {
     pub regional_pricing: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
+    /// Free-form labels matched by `--select` and codegen output filters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Arbitrary values emitted into codegen records; never sent to Roblox
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub data: BTreeMap<String, toml::Value>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -170,6 +434,12 @@ pub struct BadgeConfig {
     pub enabled: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
+    /// Free-form labels matched by `--select` and codegen output filters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Arbitrary values emitted into codegen records; never sent to Roblox
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub data: BTreeMap<String, toml::Value>,
 }
 
 fn default_true() -> bool {
@@ -203,9 +473,76 @@ pub struct ProductConfig {
     pub store_page: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
+    /// Free-form labels matched by `--select` and codegen output filters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Arbitrary values emitted into codegen records; never sent to Roblox
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub data: BTreeMap<String, toml::Value>,
+    /// Time windows that override `price` or `for_sale`, e.g. weekend sales
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedule: Vec<ScheduleEntry>,
+}
+
+/// A `[[products.<key>.schedule]]` window. Sync applies the overrides while
+/// `start <= now < end`, and reverts them afterwards.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScheduleEntry {
+    pub start: toml::value::Datetime,
+    pub end: toml::value::Datetime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub for_sale: Option<bool>,
 }
 
 impl Config {
+    /// Resolve the icon for a pass: the explicit `icon` if set, otherwise the
+    /// conventional path when `icons.auto` is enabled and the file exists.
+    pub fn pass_icon(&self, key: &str, config_dir: &Path) -> Option<PathBuf> {
+        let explicit = self.passes.get(key).and_then(|c| c.icon.as_deref());
+        self.resolve_icon(explicit, "passes", key, config_dir)
+    }
+
+    /// Resolve the icon for a badge. See [`Config::pass_icon`].
+    pub fn badge_icon(&self, key: &str, config_dir: &Path) -> Option<PathBuf> {
+        let explicit = self.badges.get(key).and_then(|c| c.icon.as_deref());
+        self.resolve_icon(explicit, "badges", key, config_dir)
+    }
+
+    /// Resolve the icon for a product. See [`Config::pass_icon`].
+    pub fn product_icon(&self, key: &str, config_dir: &Path) -> Option<PathBuf> {
+        let explicit = self.products.get(key).and_then(|c| c.icon.as_deref());
+        self.resolve_icon(explicit, "products", key, config_dir)
+    }
+
+    /// Tags of the entry `key` in `section`; empty for unknown keys and extras.
+    pub fn tags(&self, section: Section, key: &str) -> &[String] {
+        let tags = match section {
+            Section::Passes => self.passes.get(key).map(|c| &c.tags),
+            Section::Badges => self.badges.get(key).map(|c| &c.tags),
+            Section::Products => self.products.get(key).map(|c| &c.tags),
+            Section::Extra => None,
+        };
+        tags.map_or(&[], |t| t.as_slice())
+    }
+
+    fn resolve_icon(
+        &self,
+        explicit: Option<&Path>,
+        section: &str,
+        key: &str,
+        config_dir: &Path,
+    ) -> Option<PathBuf> {
+        if let Some(path) = explicit {
+            return Some(path.to_path_buf());
+        }
+        if self.icons.auto {
+            return self.icons.discover(config_dir, section, key);
+        }
+        None
+    }
+
     pub fn save(&self, path: &Path) -> Result<()> {
         let content = toml::to_string_pretty(self)?;
         std::fs::write(path, content)
@@ -214,13 +551,23 @@ impl Config {
     }
 
     pub fn load(path: &Path) -> Result<Self> {
+        let config = Self::load_allow_missing_icons(path)?;
+
+        let config_dir = path.parent().unwrap_or(Path::new("."));
+        config.validate_icon_paths(config_dir)?;
+
+        Ok(config)
+    }
+
+    /// Like [`Config::load`], but does not require configured icon files to
+    /// exist. Used by commands that report missing icons themselves.
+    pub fn load_allow_missing_icons(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read {}", path.display()))?;
         let config: Config = toml::from_str(&content)
             .with_context(|| format!("Failed to parse {}", path.display()))?;
 
-        let config_dir = path.parent().unwrap_or(Path::new("."));
-        config.validate_icon_paths(config_dir)?;
+        crate::schedule::validate(&config)?;
 
         Ok(config)
     }
@@ -278,10 +625,33 @@ id = 0                 # Your Roblox user or group ID
 # Codegen - generate a Luau module with asset IDs
 # [codegen]
 # output = "src/shared/GameIds.luau"
+# output_instance = "ReplicatedStorage.Shared.GameIds"  # Or derive output from the Rojo project
+# rojo_project = "default.project.json"  # Project to check outputs against
 # typescript = false            # Also generate a .d.ts file
+# typescript_output = "src/shared/GameIds.d.ts"  # .d.ts location (implies typescript)
+# roblox_ts = "src/shared/GameIds"  # roblox-ts package: init.luau + index.d.ts
+# model = "GameIds.rbxmx"      # Model with a ModuleScript for Studio (.rbxmx or .rbxm)
 # style = "flat"               # "flat" (default) or "nested"
 #                              # flat:   GameIds["passes.VIP"] — path-like keys
 #                              # nested: GameIds.passes.VIP   — nested tables
+# key_case = "preserve"        # "preserve", "camel", "pascal", "snake" or "screaming_snake"
+# records = false              # Emit { id, name, price, ... } records instead of IDs
+# reverse = "path"             # Add a byId table: "key" or "path" (omit to disable)
+# strict = false               # --!strict module with exported types and frozen tables
+# marketplace = "src/server/Marketplace.luau"  # Typed MarketplaceService wrapper
+# manifest = "catalog.json"    # JSON (or .yaml) manifest for backend services
+#
+# More outputs — each with its own path, language, style, paths and filters
+# [[codegen.outputs]]
+# path = "src/client/ClientIds.luau"
+# language = "luau"            # "luau", "typescript", "roblox-ts" or "model"
+# include = ["passes", "products"]  # resource types or "tag:<name>"
+# exclude = ["tag:internal"]
+#
+# User templates — rendered with the generated tree and lockfile data
+# [[codegen.templates]]
+# template = "templates/Catalog.luau.jinja"
+# output = "src/shared/Catalog.luau"
 #
 # Custom paths — dot-separated, used as prefix (flat) or nesting (nested)
 # [codegen.paths]
@@ -296,6 +666,13 @@ id = 0                 # Your Roblox user or group ID
 # [icons]
 # bleed = true         # Apply alpha bleed (fixes resize artifacts)
 # dir = "icons"        # Directory for downloaded icons
+# auto = false         # Find icons at icons/<passes|badges|products>/<key>.png
+
+# [guardrails]
+# max_price_change_percent = 50   # Larger changes need --allow-price-change
+# forbid_pass_price_increase = false
+# max_badge_spend = 0             # Robux a sync may spend once free badges run out
+# badge_cost = 100                # Robux per badge once free badges run out
 
 # Game Passes
 # [passes.VIP]
@@ -325,6 +702,12 @@ id = 0                 # Your Roblox user or group ID
 # regional_pricing = false # optional — defaults to false
 # store_page = false       # optional — defaults to false
 # path = "shop.specials"   # optional — override codegen path
+# data = { coins = 100 }   # optional — extra fields for codegen records
+#
+# [[products.Coins100.schedule]]   # optional — time-limited overrides
+# start = 2026-10-31T00:00:00Z
+# end = 2026-11-02T00:00:00Z
+# price = 49
 "#
         .to_string()
     }
@@ -5,6 +5,7 @@ use anyhow::Result;
 
 use crate::config::{resolve_name, Config};
 use crate::lockfile::Lockfile;
+use crate::select::Section;
 
 #[derive(Debug)]
 pub struct SyncPlan {
@@ -49,6 +50,14 @@ impl SyncPlan {
             .any(|a| !matches!(a.action, Action::Skip))
     }
 
+    /// Drop the actions for resources `keep` rejects, so they are neither
+    /// shown nor applied.
+    pub fn retain(&mut self, mut keep: impl FnMut(Section, &str) -> bool) {
+        self.passes.retain(|a| keep(Section::Passes, &a.name));
+        self.badges.retain(|a| keep(Section::Badges, &a.name));
+        self.products.retain(|a| keep(Section::Products, &a.name));
+    }
+
     pub fn summary(&self) -> String {
         let mut creates = 0;
         let mut updates = 0;
@@ -69,7 +78,7 @@ impl SyncPlan {
     }
 }
 
-fn hash_file(path: &Path) -> Result<String> {
+pub(crate) fn hash_file(path: &Path) -> Result<String> {
     let bytes = std::fs::read(path)?;
     Ok(blake3::hash(&bytes).to_hex().to_string())
 }
@@ -177,7 +186,7 @@ fn diff_passes(
                     });
                 }
 
-                if let Some(icon) = &pass_cfg.icon {
+                if let Some(icon) = config.pass_icon(name, config_dir) {
                     let full_path = config_dir.join(icon);
                     let current_hash = hash_file(&full_path)?;
                     let lock_hash = lock.icon_hash.as_deref().unwrap_or("");
@@ -252,7 +261,7 @@ fn diff_badges(
                 }
 
                 // Badge icon is tracked separately
-                if let Some(icon) = &badge_cfg.icon {
+                if let Some(icon) = config.badge_icon(name, config_dir) {
                     let full_path = config_dir.join(icon);
                     let current_hash = hash_file(&full_path)?;
                     let lock_hash = lock.icon_hash.as_deref().unwrap_or("");
@@ -347,7 +356,7 @@ fn diff_products(
                     });
                 }
 
-                if let Some(icon) = &product_cfg.icon {
+                if let Some(icon) = config.product_icon(name, config_dir) {
                     let full_path = config_dir.join(icon);
                     let current_hash = hash_file(&full_path)?;
                     let lock_hash = lock.icon_hash.as_deref().unwrap_or("");
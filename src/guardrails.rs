@@ -0,0 +1,187 @@
+//! Guardrails from `[guardrails]`, evaluated on a `SyncPlan` before `sync`
+//! makes any change: price change limits and the badge creation budget.
+
+use std::fmt;
+
+use anyhow::{bail, Result};
+
+use crate::config::{Config, GuardrailsConfig, ProductConfig};
+use crate::diff::{Action, ResourceAction, SyncPlan};
+use crate::lockfile::Lockfile;
+
+/// A planned price change that breaks a guardrail.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PriceViolation {
+    pub resource: &'static str,
+    pub key: String,
+    pub old: u64,
+    pub new: u64,
+    pub reason: String,
+    /// Whether `--allow-price-change` lets the change through
+    pub overridable: bool,
+}
+
+impl fmt::Display for PriceViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}': price {} -> {} {}",
+            self.resource, self.key, self.old, self.new, self.reason
+        )
+    }
+}
+
+fn changes_price(action: &ResourceAction) -> bool {
+    match &action.action {
+        Action::Update { changes } => changes.iter().any(|c| c.field == "price"),
+        Action::Create | Action::Skip => false,
+    }
+}
+
+/// Threshold checks shared by passes and products.
+fn threshold_violation(guardrails: &GuardrailsConfig, old: u64, new: u64) -> Option<String> {
+    let delta = old.abs_diff(new);
+    if let Some(max) = guardrails.max_price_change {
+        if delta > max {
+            return Some(format!(
+                "changes by {delta} Robux (max_price_change = {max})"
+            ));
+        }
+    }
+    if let Some(max) = guardrails.max_price_change_percent {
+        // Any change from a price of 0 is an unbounded percentage.
+        if delta > 0 && (old == 0 || delta * 100 > old * max) {
+            let percent = (delta * 100)
+                .checked_div(old)
+                .map_or("from 0".to_string(), |p| format!("by {p}%"));
+            return Some(format!(
+                "changes {percent} (max_price_change_percent = {max})"
+            ));
+        }
+    }
+    None
+}
+
+/// Every price change in `plan` that breaks a guardrail. Old prices come
+/// from the lockfile and new ones from the config. Passes moving to or from
+/// having no price aren't compared.
+pub fn price_violations(
+    plan: &SyncPlan,
+    config: &Config,
+    lockfile: &Lockfile,
+) -> Vec<PriceViolation> {
+    let guardrails = &config.guardrails;
+    let mut violations = Vec::new();
+
+    for action in plan.passes.iter().filter(|a| changes_price(a)) {
+        let old = lockfile.passes.get(&action.name).and_then(|l| l.price);
+        let new = config.passes.get(&action.name).and_then(|c| c.price);
+        let (Some(old), Some(new)) = (old, new) else {
+            continue;
+        };
+        let violation = |reason, overridable| PriceViolation {
+            resource: "pass",
+            key: action.name.clone(),
+            old,
+            new,
+            reason,
+            overridable,
+        };
+        if guardrails.forbid_pass_price_increase && new > old {
+            violations.push(violation(
+                "raises a pass price (forbid_pass_price_increase = true)".to_string(),
+                false,
+            ));
+        } else if let Some(reason) = threshold_violation(guardrails, old, new) {
+            violations.push(violation(reason, true));
+        }
+    }
+
+    for action in plan.products.iter().filter(|a| changes_price(a)) {
+        let (Some(lock), Some(product)) = (
+            lockfile.products.get(&action.name),
+            config.products.get(&action.name),
+        ) else {
+            continue;
+        };
+        if is_schedule_transition(product, lock.price) {
+            continue;
+        }
+        if let Some(reason) = threshold_violation(guardrails, lock.price, product.price) {
+            violations.push(PriceViolation {
+                resource: "product",
+                key: action.name.clone(),
+                old: lock.price,
+                new: product.price,
+                reason,
+                overridable: true,
+            });
+        }
+    }
+
+    violations
+}
+
+/// Whether a product's price change comes from one of its schedule windows
+/// opening or closing, i.e. the old or the new price is a scheduled price.
+/// Sales are expected to cross the thresholds, so these aren't checked.
+fn is_schedule_transition(product: &ProductConfig, old: u64) -> bool {
+    product
+        .schedule
+        .iter()
+        .filter_map(|entry| entry.price)
+        .any(|price| price == old || price == product.price)
+}
+
+/// Fail if a price change in `plan` breaks a guardrail, unless it's one
+/// `--allow-price-change` lets through.
+pub fn check_price_changes(
+    plan: &SyncPlan,
+    config: &Config,
+    lockfile: &Lockfile,
+    allow_price_change: bool,
+) -> Result<()> {
+    let blocked = price_violations(plan, config, lockfile)
+        .iter()
+        .filter(|v| !v.overridable || !allow_price_change)
+        .count();
+    if blocked > 0 {
+        bail!(
+            "{blocked} price change(s) break the [guardrails]; nothing was synced.\n  \
+             Fix the prices, skip them, or rerun with --allow-price-change to apply changes over the thresholds."
+        );
+    }
+    Ok(())
+}
+
+/// Robux Roblox charges for each badge created once the universe's free
+/// daily quota is used up, per its badge creation docs. `guardrails.badge_cost`
+/// overrides it if the price changes.
+pub const BADGE_COST: u64 = 100;
+
+/// The Robux charged per badge beyond the free quota for `config`.
+pub fn badge_cost(config: &Config) -> u64 {
+    config.guardrails.badge_cost.unwrap_or(BADGE_COST)
+}
+
+/// The expected cost of each of `creates` badge creations, in order, when
+/// `free_quota` badges can still be created for free today and the rest cost
+/// `cost` each.
+pub fn badge_costs(creates: usize, free_quota: u64, cost: u64) -> Vec<u64> {
+    (0..creates as u64)
+        .map(|i| if i < free_quota { 0 } else { cost })
+        .collect()
+}
+
+/// Fail if creating badges for `spend` Robux would exceed `max_badge_spend`.
+pub fn check_badge_budget(config: &Config, spend: u64) -> Result<()> {
+    if let Some(max) = config.guardrails.max_badge_spend {
+        if spend > max {
+            bail!(
+                "Creating these badges would cost R${spend}, over max_badge_spend = {max}; no badges were created.\n  \
+                 Raise the budget, sync fewer badges, or wait for the free badge quota to reset."
+            );
+        }
+    }
+    Ok(())
+}
@@ -1,13 +1,22 @@
 use std::io::Cursor;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use anyhow::{Context, Result};
-use image::ImageFormat;
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
 
 use crate::alpha_bleed;
 
-/// Loads an icon from disk, optionally applies alpha bleed, and returns processed PNG bytes.
-pub fn process_icon(path: &Path, bleed: bool) -> Result<Vec<u8>> {
+/// Size of a single tile in an icon contact sheet. Matches the size Roblox
+/// displays badge icons at.
+pub const PREVIEW_TILE_SIZE: u32 = 150;
+
+const PREVIEW_PADDING: u32 = 10;
+const PREVIEW_BACKGROUND: Rgba<u8> = Rgba([0x2b, 0x2d, 0x31, 0xff]);
+
+/// Loads an icon from disk and optionally applies alpha bleed.
+pub fn load_icon(path: &Path, bleed: bool) -> Result<DynamicImage> {
     let mut img =
         image::open(path).with_context(|| format!("Failed to open icon: {}", path.display()))?;
 
@@ -15,9 +24,166 @@ pub fn process_icon(path: &Path, bleed: bool) -> Result<Vec<u8>> {
         alpha_bleed::alpha_bleed(&mut img);
     }
 
+    Ok(img)
+}
+
+/// Loads an icon from disk, optionally applies alpha bleed, and returns processed PNG bytes.
+pub fn process_icon(path: &Path, bleed: bool) -> Result<Vec<u8>> {
+    let img = load_icon(path, bleed)?;
+
     let mut buf = Vec::new();
     img.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
         .with_context(|| format!("Failed to encode icon: {}", path.display()))?;
 
     Ok(buf)
 }
+
+/// Processes several icons in parallel, returning their PNG bytes in the same
+/// order as `paths`.
+pub fn process_icons(paths: &[PathBuf], bleed: bool) -> Result<Vec<Vec<u8>>> {
+    let threads = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(paths.len());
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<(usize, Result<Vec<u8>>)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = paths.get(i) else {
+                            break;
+                        };
+                        done.push((i, process_icon(path, bleed)));
+                    }
+                    done
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("icon worker panicked"))
+            .collect()
+    });
+
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, bytes)| bytes).collect()
+}
+
+/// How Roblox crops an icon when displaying it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconShape {
+    /// Badges are rendered as a circle inscribed in the image.
+    Circle,
+    /// Passes and products are rendered as-is.
+    Square,
+}
+
+/// Returns true if the pixel at `(x, y)` lies inside the circle inscribed in
+/// a `w` x `h` image. Pixels are sampled at their centers.
+fn inside_circle(x: u32, y: u32, w: u32, h: u32) -> bool {
+    let radius = w.min(h) as f64 / 2.0;
+    let dx = x as f64 + 0.5 - w as f64 / 2.0;
+    let dy = y as f64 + 0.5 - h as f64 / 2.0;
+    dx * dx + dy * dy <= radius * radius
+}
+
+/// Makes every pixel outside the inscribed circle fully transparent.
+pub fn apply_circle_mask(img: &mut RgbaImage) {
+    let (w, h) = img.dimensions();
+    for (x, y, pixel) in img.enumerate_pixels_mut() {
+        if !inside_circle(x, y, w, h) {
+            pixel[3] = 0;
+        }
+    }
+}
+
+/// Alpha at or below which a pixel outside the circle is treated as edge
+/// anti-aliasing rather than content. An anti-aliased edge pixel whose center
+/// lies outside the circle is covered by less than half, so stays below this.
+const SAFE_AREA_ALPHA_CUTOFF: u8 = 127;
+
+/// Fraction of the area outside the circle that may hold visible pixels
+/// before a badge icon is flagged.
+const SAFE_AREA_TOLERANCE: f64 = 0.005;
+
+/// Returns the fraction (0.0–1.0) of the area outside the inscribed circle
+/// covered by pixels with alpha above `alpha_cutoff`.
+pub fn visible_outside_circle(img: &RgbaImage, alpha_cutoff: u8) -> f64 {
+    let (w, h) = img.dimensions();
+    let mut area = 0u64;
+    let mut visible = 0u64;
+
+    for (x, y, pixel) in img.enumerate_pixels() {
+        if inside_circle(x, y, w, h) {
+            continue;
+        }
+        area += 1;
+        if pixel[3] > alpha_cutoff {
+            visible += 1;
+        }
+    }
+
+    if area == 0 {
+        0.0
+    } else {
+        visible as f64 / area as f64
+    }
+}
+
+/// Returns a warning if a badge icon has visible content outside the circle.
+///
+/// Faint edge pixels and a small share of the outside area are tolerated so
+/// that anti-aliased circular art is not flagged.
+pub fn lint_badge_safe_area(img: &RgbaImage) -> Option<String> {
+    let fraction = visible_outside_circle(img, SAFE_AREA_ALPHA_CUTOFF);
+    if fraction > SAFE_AREA_TOLERANCE {
+        Some(format!(
+            "{:.1}% of the area outside the visible circle has content that will be cropped",
+            fraction * 100.0
+        ))
+    } else {
+        None
+    }
+}
+
+/// Resizes an icon to a preview tile and applies the crop Roblox uses for `shape`.
+pub fn preview_tile(img: &DynamicImage, shape: IconShape) -> RgbaImage {
+    let mut tile = imageops::resize(
+        &img.to_rgba8(),
+        PREVIEW_TILE_SIZE,
+        PREVIEW_TILE_SIZE,
+        FilterType::Lanczos3,
+    );
+    if shape == IconShape::Circle {
+        apply_circle_mask(&mut tile);
+    }
+    tile
+}
+
+/// Lays out preview tiles in a grid with `columns` tiles per row.
+pub fn contact_sheet(tiles: &[RgbaImage], columns: u32) -> RgbaImage {
+    let columns = columns.max(1);
+    let count = tiles.len() as u32;
+    let cols = count.clamp(1, columns);
+    let rows = count.div_ceil(columns).max(1);
+    let cell = PREVIEW_TILE_SIZE + PREVIEW_PADDING;
+
+    let mut sheet = RgbaImage::from_pixel(
+        cols * cell + PREVIEW_PADDING,
+        rows * cell + PREVIEW_PADDING,
+        PREVIEW_BACKGROUND,
+    );
+
+    for (i, tile) in tiles.iter().enumerate() {
+        let i = i as u32;
+        let x = PREVIEW_PADDING + (i % columns) * cell;
+        let y = PREVIEW_PADDING + (i / columns) * cell;
+        imageops::overlay(&mut sheet, tile, x as i64, y as i64);
+    }
+
+    sheet
+}
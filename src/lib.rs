@@ -5,5 +5,8 @@ pub mod codegen;
 pub mod commands;
 pub mod config;
 pub mod diff;
+pub mod guardrails;
 pub mod icon;
 pub mod lockfile;
+pub mod schedule;
+pub mod select;
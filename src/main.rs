@@ -1,6 +1,6 @@
 use anyhow::Result;
 use clap::Parser;
-use rbxsync::cli::{Cli, Commands};
+use rbxsync::cli::{Cli, Commands, IconsCommand};
 use rbxsync::commands;
 
 #[tokio::main]
@@ -13,21 +13,50 @@ async fn main() -> Result<()> {
             universe_id,
         } => commands::init::run(&cli, *from_remote, *universe_id).await,
         Commands::Sync {
+            selectors,
             dry_run,
             only,
+            at,
+            allow_price_change,
             badge_cost,
-        } => commands::sync::run(&cli, *dry_run, only.clone(), *badge_cost).await,
+            yes,
+        } => {
+            commands::sync::run(
+                &cli,
+                selectors,
+                *dry_run,
+                only.clone(),
+                at.as_deref(),
+                *allow_price_change,
+                *badge_cost,
+                *yes,
+            )
+            .await
+        }
         Commands::List { resource } => commands::list::run(&cli, resource.clone()).await,
         Commands::Check => commands::check::run(&cli).await,
         Commands::Pull {
+            selectors,
             dry_run,
             accept_remote,
             accept_local,
-        } => commands::pull::run(&cli, *dry_run, *accept_remote, *accept_local).await,
+        } => commands::pull::run(&cli, selectors, *dry_run, *accept_remote, *accept_local).await,
+        Commands::Codegen { check } => commands::codegen::run(&cli, *check),
         Commands::Rename {
             resource,
             old_key,
             new_key,
         } => commands::rename::run(&cli, resource.clone(), old_key, new_key),
+        Commands::Icons { command } => match command {
+            IconsCommand::Preview { output, columns } => {
+                commands::icons::preview(&cli, output, *columns)
+            }
+            IconsCommand::Report { output } => commands::icons::report(&cli, output),
+            IconsCommand::Gc {
+                dry_run,
+                delete,
+                move_to,
+            } => commands::icons::gc(&cli, *dry_run, *delete, move_to.as_deref()),
+        },
     }
 }
@@ -0,0 +1,174 @@
+//! Time-windowed overrides from `[[products.<key>.schedule]]`: resolving the
+//! values in effect at a given time and listing upcoming transitions.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use toml::value::{Datetime, Offset};
+
+use crate::config::{Config, ProductConfig, ScheduleEntry};
+
+/// Current time as seconds since the Unix epoch.
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Seconds since the Unix epoch for a full datetime with a UTC offset.
+pub fn unix_time(datetime: &Datetime) -> Result<i64> {
+    let (Some(date), Some(time), Some(offset)) = (datetime.date, datetime.time, datetime.offset)
+    else {
+        bail!("'{datetime}' needs a date, a time and a UTC offset, e.g. 2026-10-31T00:00:00Z");
+    };
+    let offset_minutes = match offset {
+        Offset::Z => 0,
+        Offset::Custom { minutes } => i64::from(minutes),
+    };
+    let days = days_from_civil(date.year.into(), date.month.into(), date.day.into());
+    let seconds =
+        i64::from(time.hour) * 3600 + i64::from(time.minute) * 60 + i64::from(time.second);
+    Ok(days * 86_400 + seconds - offset_minutes * 60)
+}
+
+/// Parse a `--at` value such as `2026-10-31T12:00:00Z`.
+pub fn parse_time(s: &str) -> Result<i64> {
+    let datetime: Datetime = s
+        .parse()
+        .with_context(|| format!("Invalid time '{s}': expected e.g. 2026-10-31T12:00:00Z"))?;
+    unix_time(&datetime)
+}
+
+impl ScheduleEntry {
+    /// Whether the window covers `at`. `start` is inclusive, `end` exclusive.
+    pub fn is_active(&self, at: i64) -> Result<bool> {
+        Ok(unix_time(&self.start)? <= at && at < unix_time(&self.end)?)
+    }
+
+    /// The overridden fields, e.g. `price 499, for_sale false`.
+    pub fn describe(&self) -> String {
+        let mut fields = Vec::new();
+        if let Some(price) = self.price {
+            fields.push(format!("price {price}"));
+        }
+        if let Some(for_sale) = self.for_sale {
+            fields.push(format!("for_sale {for_sale}"));
+        }
+        fields.join(", ")
+    }
+}
+
+impl ProductConfig {
+    /// `price` and `for_sale` with the schedule window active at `at`, if
+    /// any, applied.
+    pub fn effective_at(&self, at: i64) -> Result<(u64, bool)> {
+        for entry in &self.schedule {
+            if entry.is_active(at)? {
+                return Ok((
+                    entry.price.unwrap_or(self.price),
+                    entry.for_sale.unwrap_or(self.for_sale),
+                ));
+            }
+        }
+        Ok((self.price, self.for_sale))
+    }
+}
+
+/// Replace every product's `price` and `for_sale` with the values in effect
+/// at `at`, so the sync plan targets them.
+pub fn apply(config: &mut Config, at: i64) -> Result<()> {
+    for product in config.products.values_mut() {
+        (product.price, product.for_sale) = product.effective_at(at)?;
+    }
+    Ok(())
+}
+
+/// Check that every schedule window is well-formed and that a product's
+/// windows don't overlap.
+pub fn validate(config: &Config) -> Result<()> {
+    for (key, product) in &config.products {
+        let mut windows = Vec::new();
+        for entry in &product.schedule {
+            let start = unix_time(&entry.start)
+                .with_context(|| format!("Invalid schedule start for product '{key}'"))?;
+            let end = unix_time(&entry.end)
+                .with_context(|| format!("Invalid schedule end for product '{key}'"))?;
+            if start >= end {
+                bail!(
+                    "Schedule for product '{key}' must end ({}) after it starts ({})",
+                    entry.end,
+                    entry.start
+                );
+            }
+            if entry.price.is_none() && entry.for_sale.is_none() {
+                bail!(
+                    "Schedule for product '{key}' starting {} overrides nothing: set price or for_sale",
+                    entry.start
+                );
+            }
+            windows.push((start, end, entry));
+        }
+
+        windows.sort_by_key(|(start, _, _)| *start);
+        for pair in windows.windows(2) {
+            let (_, end, previous) = pair[0];
+            let (start, _, next) = pair[1];
+            if start < end {
+                bail!(
+                    "Schedules for product '{key}' overlap: {}..{} and {}..{}",
+                    previous.start,
+                    previous.end,
+                    next.start,
+                    next.end
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A scheduled change to a product's effective values.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Transition {
+    /// Seconds since the Unix epoch
+    pub at: i64,
+    pub time: Datetime,
+    pub key: String,
+    /// `true` when the window opens, `false` when it closes
+    pub starts: bool,
+    pub overrides: String,
+}
+
+/// Window openings and closings after `now`, in chronological order.
+pub fn upcoming_transitions(config: &Config, now: i64) -> Result<Vec<Transition>> {
+    let mut transitions = Vec::new();
+    for (key, product) in &config.products {
+        for entry in &product.schedule {
+            for (time, starts) in [(&entry.start, true), (&entry.end, false)] {
+                let at = unix_time(time)?;
+                if at > now {
+                    transitions.push(Transition {
+                        at,
+                        time: *time,
+                        key: key.clone(),
+                        starts,
+                        overrides: entry.describe(),
+                    });
+                }
+            }
+        }
+    }
+    transitions.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.key.cmp(&b.key)));
+    Ok(transitions)
+}
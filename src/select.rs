@@ -0,0 +1,337 @@
+//! Selection expressions shared by the global `--select` option and
+//! `[[codegen.outputs]] select`.
+//!
+//! An expression is a list of terms joined by `,` (all must match), with
+//! alternatives separated by `|` (any may match). A term is `tag:<name>`,
+//! `type:<section>`, `key:<glob>` or a bare key glob, optionally prefixed
+//! with `!` to negate it.
+//!
+//! `sync` and `pull` also take positional [`Selector`]s, which narrow the
+//! selection further.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ResourceType;
+
+/// The config section a resource comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Section {
+    Passes,
+    Badges,
+    Products,
+    Extra,
+}
+
+impl Section {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "passes" | "pass" => Some(Section::Passes),
+            "badges" | "badge" => Some(Section::Badges),
+            "products" | "product" => Some(Section::Products),
+            "extra" => Some(Section::Extra),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Section::Passes => "passes",
+            Section::Badges => "badges",
+            Section::Products => "products",
+            Section::Extra => "extra",
+        }
+    }
+}
+
+impl From<&ResourceType> for Section {
+    fn from(resource: &ResourceType) -> Self {
+        match resource {
+            ResourceType::Passes => Section::Passes,
+            ResourceType::Badges => Section::Badges,
+            ResourceType::Products => Section::Products,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    Tag(String),
+    Type(Section),
+    Key(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Condition {
+    negated: bool,
+    term: Term,
+}
+
+impl Condition {
+    fn matches(&self, section: Section, key: &str, tags: &[String]) -> bool {
+        let matched = match &self.term {
+            Term::Tag(tag) => tags.contains(tag),
+            Term::Type(s) => *s == section,
+            Term::Key(pattern) => glob_match(pattern, key),
+        };
+        matched != self.negated
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negated {
+            f.write_str("!")?;
+        }
+        match &self.term {
+            Term::Tag(tag) => write!(f, "tag:{tag}"),
+            Term::Type(section) => write!(f, "type:{}", section.as_str()),
+            Term::Key(pattern) => write!(f, "key:{pattern}"),
+        }
+    }
+}
+
+/// A parsed selection. The empty selection matches everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Selection {
+    alternatives: Vec<Vec<Condition>>,
+}
+
+impl Selection {
+    /// Parse repeated `--select` values; an entry matching any of them is
+    /// selected.
+    pub fn parse_all(exprs: &[String]) -> Result<Self> {
+        let mut selection = Selection::default();
+        for expr in exprs {
+            selection
+                .alternatives
+                .extend(expr.parse::<Selection>()?.alternatives);
+        }
+        Ok(selection)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.alternatives.is_empty()
+    }
+
+    /// Narrow the selection to entries that also match one of `selectors`.
+    pub fn with_selectors(self, selectors: &[Selector]) -> Self {
+        if selectors.is_empty() {
+            return self;
+        }
+        let bases = if self.is_empty() {
+            vec![Vec::new()]
+        } else {
+            self.alternatives
+        };
+        let alternatives = bases
+            .iter()
+            .flat_map(|base| {
+                selectors.iter().map(move |selector| {
+                    let mut conditions = base.clone();
+                    conditions.extend(selector.conditions());
+                    conditions
+                })
+            })
+            .collect();
+        Selection { alternatives }
+    }
+
+    /// Entries matched by both this selection and `other`.
+    pub fn and(self, other: Selection) -> Self {
+        if self.is_empty() {
+            return other;
+        }
+        if other.is_empty() {
+            return self;
+        }
+        let alternatives = self
+            .alternatives
+            .iter()
+            .flat_map(|left| {
+                other.alternatives.iter().map(move |right| {
+                    let mut conditions = left.clone();
+                    conditions.extend(right.iter().cloned());
+                    conditions
+                })
+            })
+            .collect();
+        Selection { alternatives }
+    }
+
+    /// Whether the entry `key` from `section`, carrying `tags`, is selected.
+    pub fn matches(&self, section: Section, key: &str, tags: &[String]) -> bool {
+        self.is_empty()
+            || self
+                .alternatives
+                .iter()
+                .any(|all| all.iter().all(|c| c.matches(section, key, tags)))
+    }
+}
+
+fn parse_condition(term: &str) -> Result<Condition> {
+    let (negated, term) = match term.strip_prefix('!') {
+        Some(rest) => (true, rest.trim()),
+        None => (false, term),
+    };
+    let term = if let Some(tag) = term.strip_prefix("tag:") {
+        if tag.is_empty() {
+            bail!("Empty tag in selection term '{term}'");
+        }
+        Term::Tag(tag.to_string())
+    } else if let Some(section) = term.strip_prefix("type:") {
+        match Section::parse(section) {
+            Some(section) => Term::Type(section),
+            None => bail!(
+                "Unknown type '{section}' in selection: expected passes, badges, products or extra"
+            ),
+        }
+    } else {
+        let pattern = term.strip_prefix("key:").unwrap_or(term);
+        if pattern.is_empty() {
+            bail!("Empty term in selection");
+        }
+        Term::Key(pattern.to_string())
+    };
+    Ok(Condition { negated, term })
+}
+
+impl FromStr for Selection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut alternatives = Vec::new();
+        for alternative in s.split('|') {
+            let conditions = alternative
+                .split(',')
+                .map(|term| parse_condition(term.trim()))
+                .collect::<Result<Vec<_>>>()?;
+            alternatives.push(conditions);
+        }
+        Ok(Selection { alternatives })
+    }
+}
+
+impl fmt::Display for Selection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, alternative) in self.alternatives.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" | ")?;
+            }
+            for (j, condition) in alternative.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{condition}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for Selection {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse().map_err(|e: anyhow::Error| e.to_string())
+    }
+}
+
+impl From<Selection> for String {
+    fn from(selection: Selection) -> Self {
+        selection.to_string()
+    }
+}
+
+/// A positional `<section>.<key glob>` selector, e.g. `products.Coins100`
+/// or `passes.VIP*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    pub section: Section,
+    pub pattern: String,
+}
+
+impl Selector {
+    pub fn parse_all(selectors: &[String]) -> Result<Vec<Self>> {
+        selectors.iter().map(|s| s.parse()).collect()
+    }
+
+    pub fn matches(&self, section: Section, key: &str) -> bool {
+        self.section == section && glob_match(&self.pattern, key)
+    }
+
+    fn conditions(&self) -> [Condition; 2] {
+        [
+            Condition {
+                negated: false,
+                term: Term::Type(self.section),
+            },
+            Condition {
+                negated: false,
+                term: Term::Key(self.pattern.clone()),
+            },
+        ]
+    }
+}
+
+impl FromStr for Selector {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let section = s
+            .split_once('.')
+            .filter(|(_, pattern)| !pattern.is_empty())
+            .and_then(|(section, pattern)| Some((Section::parse(section)?, pattern)));
+        match section {
+            Some((section, pattern)) if section != Section::Extra => Ok(Selector {
+                section,
+                pattern: pattern.to_string(),
+            }),
+            _ => bail!(
+                "Invalid selector '{s}': expected <passes|badges|products>.<key>, e.g. products.Coins100"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.section.as_str(), self.pattern)
+    }
+}
+
+/// Match `text` against a glob where `*` matches any run of characters and
+/// `?` matches exactly one.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it was tried at.
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, from)) => {
+                    p = star + 1;
+                    t = from + 1;
+                    backtrack = Some((star, from + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
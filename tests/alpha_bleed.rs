@@ -0,0 +1,79 @@
+#[path = "support/reference_bleed.rs"]
+mod reference;
+
+use image::{DynamicImage, ImageBuffer, Rgba, RgbaImage};
+use rbxsync::alpha_bleed::alpha_bleed;
+
+fn assert_matches_reference(img: RgbaImage) {
+    let mut expected = DynamicImage::ImageRgba8(img.clone());
+    reference::alpha_bleed(&mut expected);
+
+    let mut actual = DynamicImage::ImageRgba8(img);
+    alpha_bleed(&mut actual);
+
+    assert_eq!(actual.to_rgba8().as_raw(), expected.to_rgba8().as_raw());
+}
+
+#[test]
+fn matches_reference_on_generated_images() {
+    for (seed, (w, h)) in [(64, 64), (97, 31), (1, 200), (200, 1), (128, 128)]
+        .into_iter()
+        .enumerate()
+    {
+        assert_matches_reference(reference::sample_image(w, h, seed as u64));
+    }
+}
+
+#[test]
+fn matches_reference_on_edge_cases() {
+    // Fully transparent, fully opaque, single pixel, empty
+    assert_matches_reference(RgbaImage::new(16, 16));
+    assert_matches_reference(RgbaImage::from_pixel(16, 16, Rgba([1, 2, 3, 255])));
+    assert_matches_reference(RgbaImage::from_pixel(1, 1, Rgba([9, 9, 9, 0])));
+    assert_matches_reference(RgbaImage::new(0, 0));
+}
+
+#[test]
+fn bleeds_color_into_transparent_pixels() {
+    let mut img = RgbaImage::new(5, 1);
+    img.put_pixel(0, 0, Rgba([200, 100, 50, 255]));
+
+    let mut dynamic = DynamicImage::ImageRgba8(img);
+    alpha_bleed(&mut dynamic);
+    let out = dynamic.to_rgba8();
+
+    for x in 1..5 {
+        assert_eq!(*out.get_pixel(x, 0), Rgba([200, 100, 50, 0]));
+    }
+}
+
+#[test]
+fn preserves_non_rgba8_formats() {
+    let img = reference::sample_image(48, 48, 7);
+    let rgba16 = DynamicImage::ImageRgba8(img.clone()).to_rgba16();
+
+    let mut expected = DynamicImage::ImageRgba16(rgba16.clone());
+    reference::alpha_bleed(&mut expected);
+
+    let mut actual = DynamicImage::ImageRgba16(rgba16);
+    alpha_bleed(&mut actual);
+
+    let (DynamicImage::ImageRgba16(actual), DynamicImage::ImageRgba16(expected)) =
+        (actual, expected)
+    else {
+        panic!("alpha bleed changed the pixel format");
+    };
+    assert_eq!(actual.as_raw(), expected.as_raw());
+}
+
+#[test]
+fn leaves_unreached_wide_pixels_untouched() {
+    // A fully transparent image has nothing to bleed from, so colors that
+    // don't survive a round trip through u8 must be kept as they are.
+    let rgba16 = ImageBuffer::from_pixel(8, 8, Rgba([0x1234u16, 0x5678, 0x9abc, 0]));
+
+    let mut img = DynamicImage::ImageRgba16(rgba16.clone());
+    alpha_bleed(&mut img);
+
+    assert_eq!(img.as_rgba16().unwrap().as_raw(), rgba16.as_raw());
+}
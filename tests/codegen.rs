@@ -1,12 +1,18 @@
+use rbxsync::cli::{Cli, Commands};
+use rbxsync::codegen::manifest::{Manifest, ManifestResourceType, MANIFEST_VERSION};
+use rbxsync::codegen::rojo::RojoProject;
 use rbxsync::codegen::{
-    build_tree, build_tree_default, build_tree_default_flat, format_key, generate_luau,
-    generate_typescript, is_valid_luau_identifier,
+    build_tree, build_tree_default, build_tree_default_flat, format_key, generate, generate_luau,
+    generate_luau_with, generate_manifest, generate_marketplace, generate_model,
+    generate_roblox_ts, generate_template, generate_typescript, generate_typescript_with,
+    is_valid_luau_identifier, path_collisions, render, render_luau, render_template,
+    resolve_output, reverse_lookup, rojo_warnings, transform_key, CodegenTree, RenderOptions,
 };
 use rbxsync::config::{
-    BadgeConfig, CodegenConfig, CodegenPaths, CodegenStyle, Config, Creator, CreatorType,
-    Experience, IconsConfig, PassConfig, ProductConfig,
+    BadgeConfig, CodegenConfig, CodegenOutput, CodegenPaths, CodegenStyle, Config, Creator,
+    CreatorType, Experience, IconsConfig, KeyCase, PassConfig, ProductConfig, ReverseLookup,
 };
-use rbxsync::lockfile::{BadgeLock, Lockfile, PassLock, ProductLock};
+use rbxsync::lockfile::{BadgeLock, Lockfile, PassLock, ProductLock, LOCKFILE_NAME};
 use std::collections::BTreeMap;
 
 #[test]
@@ -202,12 +208,26 @@ fn test_config_full(
         },
         codegen: CodegenConfig {
             output: None,
+            output_instance: None,
+            rojo_project: None,
             typescript: false,
+            typescript_output: None,
+            roblox_ts: None,
+            model: None,
             style,
             paths: codegen_paths,
+            key_case: KeyCase::Preserve,
             extra,
+            records: false,
+            reverse: None,
+            strict: false,
+            marketplace: None,
+            manifest: None,
+            outputs: Vec::new(),
+            templates: Vec::new(),
         },
         icons: IconsConfig::default(),
+        guardrails: Default::default(),
         passes,
         badges,
         products,
@@ -300,6 +320,8 @@ fn generate_luau_with_section_paths() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -314,11 +336,14 @@ fn generate_luau_with_section_paths() {
                 regional_pricing: false,
                 store_page: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
+                schedule: Vec::new(),
             },
         )]),
     );
 
-    let tree = build_tree(&lockfile, &config);
+    let tree = build_tree(&lockfile, &config).unwrap();
     generate_luau(&tree, &output).unwrap();
     let content = std::fs::read_to_string(&output).unwrap();
 
@@ -388,6 +413,9 @@ fn generate_luau_with_item_path_override() {
                     regional_pricing: false,
                     store_page: false,
                     path: Some("shop.specials".into()),
+                    tags: Vec::new(),
+                    data: BTreeMap::new(),
+                    schedule: Vec::new(),
                 },
             ),
             (
@@ -401,12 +429,15 @@ fn generate_luau_with_item_path_override() {
                     regional_pricing: false,
                     store_page: false,
                     path: None,
+                    tags: Vec::new(),
+                    data: BTreeMap::new(),
+                    schedule: Vec::new(),
                 },
             ),
         ]),
     );
 
-    let tree = build_tree(&lockfile, &config);
+    let tree = build_tree(&lockfile, &config).unwrap();
     generate_luau(&tree, &output).unwrap();
     let content = std::fs::read_to_string(&output).unwrap();
 
@@ -471,6 +502,8 @@ fn generate_luau_nested_path_merging() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -485,11 +518,14 @@ fn generate_luau_nested_path_merging() {
                 regional_pricing: false,
                 store_page: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
+                schedule: Vec::new(),
             },
         )]),
     );
 
-    let tree = build_tree(&lockfile, &config);
+    let tree = build_tree(&lockfile, &config).unwrap();
     generate_luau(&tree, &output).unwrap();
     let content = std::fs::read_to_string(&output).unwrap();
 
@@ -645,6 +681,8 @@ fn generate_luau_flat_with_custom_paths() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -660,6 +698,9 @@ fn generate_luau_flat_with_custom_paths() {
                     regional_pricing: false,
                     store_page: false,
                     path: None,
+                    tags: Vec::new(),
+                    data: BTreeMap::new(),
+                    schedule: Vec::new(),
                 },
             ),
             (
@@ -673,12 +714,15 @@ fn generate_luau_flat_with_custom_paths() {
                     regional_pricing: false,
                     store_page: false,
                     path: Some("shop.specials".into()),
+                    tags: Vec::new(),
+                    data: BTreeMap::new(),
+                    schedule: Vec::new(),
                 },
             ),
         ]),
     );
 
-    let tree = build_tree(&lockfile, &config);
+    let tree = build_tree(&lockfile, &config).unwrap();
     generate_luau(&tree, &output).unwrap();
     let content = std::fs::read_to_string(&output).unwrap();
 
@@ -736,7 +780,7 @@ fn generate_luau_extra_flat() {
         BTreeMap::new(),
     );
 
-    let tree = build_tree(&lockfile, &config);
+    let tree = build_tree(&lockfile, &config).unwrap();
     generate_luau(&tree, &output).unwrap();
     let content = std::fs::read_to_string(&output).unwrap();
 
@@ -765,7 +809,7 @@ fn generate_luau_extra_nested() {
         BTreeMap::new(),
     );
 
-    let tree = build_tree(&lockfile, &config);
+    let tree = build_tree(&lockfile, &config).unwrap();
     generate_luau(&tree, &output).unwrap();
     let content = std::fs::read_to_string(&output).unwrap();
 
@@ -810,13 +854,15 @@ fn generate_luau_extra_mixed_with_synced() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
         BTreeMap::new(),
     );
 
-    let tree = build_tree(&lockfile, &config);
+    let tree = build_tree(&lockfile, &config).unwrap();
     generate_luau(&tree, &output).unwrap();
     let content = std::fs::read_to_string(&output).unwrap();
 
@@ -843,7 +889,7 @@ fn extra_no_dot_inserts_at_root() {
         BTreeMap::new(),
     );
 
-    let tree = build_tree(&lockfile, &config);
+    let tree = build_tree(&lockfile, &config).unwrap();
     generate_luau(&tree, &output).unwrap();
     let content = std::fs::read_to_string(&output).unwrap();
 
@@ -872,3 +918,1536 @@ output = "GameIds.luau"
     assert_eq!(config.codegen.extra["passes.legacy_vip"], 1234567);
     assert_eq!(config.codegen.extra["products.starter_pack"], 9876543);
 }
+
+// ---------------------------------------------------------------------------
+// Record tests
+// ---------------------------------------------------------------------------
+
+fn records_lockfile() -> Lockfile {
+    let mut lockfile = Lockfile::default();
+    lockfile.passes.insert(
+        "VIP".into(),
+        PassLock {
+            id: 111,
+            name: "VIP \"Gold\"".into(),
+            price: Some(499),
+            description: Some("Line one\nLine two".into()),
+            icon_asset_id: Some(4242),
+            icon_hash: None,
+            for_sale: true,
+            regional_pricing: false,
+        },
+    );
+    lockfile.badges.insert(
+        "Welcome".into(),
+        BadgeLock {
+            id: 222,
+            name: "Welcome".into(),
+            description: None,
+            enabled: true,
+            icon_asset_id: None,
+            icon_hash: None,
+        },
+    );
+    lockfile
+}
+
+#[test]
+fn generate_luau_records_flat() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("GameIds.luau");
+
+    let mut config = test_config_full(
+        CodegenStyle::Flat,
+        CodegenPaths::default(),
+        BTreeMap::from([("passes.legacy".into(), 999)]),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+    );
+    config.codegen.records = true;
+
+    let tree = build_tree(&records_lockfile(), &config).unwrap();
+    generate_luau_with(&tree, &output, &RenderOptions::from(&config.codegen)).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.contains("export type AssetRecord = {"));
+    assert!(content.contains("\ticonId: string?,"));
+    assert!(content.contains("\t[\"passes.VIP\"] = {\n\t\tid = 111,\n"));
+    assert!(content.contains(r#"name = "VIP \"Gold\"","#));
+    assert!(content.contains("price = 499,"));
+    assert!(content.contains(r#"description = "Line one\nLine two","#));
+    assert!(content.contains(r#"iconId = "rbxassetid://4242","#));
+    assert!(content.contains("forSale = true,"));
+    // Badges have no price or sale state
+    assert!(content
+        .contains("\t[\"badges.Welcome\"] = {\n\t\tid = 222,\n\t\tname = \"Welcome\",\n\t},"));
+    // Extra entries only carry an ID
+    assert!(content.contains("\t[\"passes.legacy\"] = {\n\t\tid = 999,\n\t},"));
+}
+
+#[test]
+fn generate_luau_records_nested() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("GameIds.luau");
+
+    let tree = build_tree_default(&records_lockfile());
+    let options = RenderOptions {
+        records: true,
+        ..Default::default()
+    };
+    generate_luau_with(&tree, &output, &options).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.contains("\tpasses = {\n\t\tVIP = {\n\t\t\tid = 111,\n"));
+    assert!(content.contains("\t\t\tforSale = true,\n\t\t},\n\t},"));
+    // The table is annotated with the record type even without strict mode
+    assert!(!content.contains("--!strict"));
+    assert!(content.contains("export type GameIds = {\n\tbadges: {\n\t\tWelcome: AssetRecord,\n"));
+    assert!(content.contains("local GameIds: GameIds = {\n"));
+}
+
+#[test]
+fn generate_luau_without_records_is_unchanged() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("GameIds.luau");
+
+    let tree = build_tree_default_flat(&records_lockfile());
+    generate_luau(&tree, &output).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(!content.contains("AssetRecord"));
+    assert!(content.contains(r#"["passes.VIP"] = 111,"#));
+}
+
+#[test]
+fn generate_typescript_records() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("GameIds.d.ts");
+
+    let tree = build_tree_default(&records_lockfile());
+    let options = RenderOptions {
+        records: true,
+        ..Default::default()
+    };
+    generate_typescript_with(&tree, &output, &options).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.contains("\t\tVIP: GameIds.AssetRecord\n"));
+    assert!(content.contains("declare namespace GameIds {\n"));
+    assert!(content.contains("\tinterface AssetRecord {\n"));
+    assert!(content.contains("\t\treadonly iconId?: string\n"));
+    assert!(content.ends_with("export = GameIds\n"));
+}
+
+#[test]
+fn records_parses_from_toml() {
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[codegen]
+output = "GameIds.luau"
+records = true
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    assert!(config.codegen.records);
+}
+
+// ---------------------------------------------------------------------------
+// Reverse lookup tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn generate_luau_reverse_paths_nested() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("GameIds.luau");
+
+    let config = test_config_full(
+        CodegenStyle::Nested,
+        CodegenPaths::default(),
+        BTreeMap::from([("shop.legacy".into(), 999)]),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+    );
+    let tree = build_tree(&records_lockfile(), &config).unwrap();
+    let options = RenderOptions {
+        reverse: Some(ReverseLookup::Path),
+        ..Default::default()
+    };
+    generate_luau_with(&tree, &output, &options).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.contains(
+        "\tbyId = {\n\t\t[111] = \"passes.VIP\",\n\t\t[222] = \"badges.Welcome\",\n\t\t[999] = \"shop.legacy\",\n\t},"
+    ));
+}
+
+#[test]
+fn reverse_lookup_by_key() {
+    let config = test_config_full(
+        CodegenStyle::Flat,
+        CodegenPaths::default(),
+        BTreeMap::from([("shop.legacy".into(), 999)]),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+    );
+    let tree = build_tree(&records_lockfile(), &config).unwrap();
+    let table = reverse_lookup(&tree, ReverseLookup::Key).unwrap();
+
+    assert_eq!(table[&111], "VIP");
+    assert_eq!(table[&222], "Welcome");
+    assert_eq!(table[&999], "legacy");
+}
+
+#[test]
+fn generate_typescript_reverse_union() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("GameIds.d.ts");
+
+    let tree = build_tree_default_flat(&records_lockfile());
+    let options = RenderOptions {
+        reverse: Some(ReverseLookup::Path),
+        ..Default::default()
+    };
+    generate_typescript_with(&tree, &output, &options).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.contains(
+        "\tbyId: {\n\t\treadonly [id: number]: \"badges.Welcome\" | \"passes.VIP\"\n\t}\n"
+    ));
+}
+
+#[test]
+fn reverse_lookup_rejects_duplicate_extra_ids() {
+    let config = test_config_full(
+        CodegenStyle::Flat,
+        CodegenPaths::default(),
+        BTreeMap::from([("passes.old_vip".into(), 111)]),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+    );
+    let tree = build_tree(&records_lockfile(), &config).unwrap();
+    let err = reverse_lookup(&tree, ReverseLookup::Path)
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("Duplicate ID 111"));
+    assert!(err.contains("codegen.extra 'passes.old_vip'"));
+    assert!(err.contains("pass 'VIP'"));
+}
+
+#[test]
+fn reverse_lookup_rejects_by_id_key() {
+    let config = test_config_full(
+        CodegenStyle::Nested,
+        CodegenPaths::default(),
+        BTreeMap::from([("byId".into(), 1)]),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+    );
+    let tree = build_tree(&Lockfile::default(), &config).unwrap();
+    assert!(reverse_lookup(&tree, ReverseLookup::Key).is_err());
+}
+
+// ---------------------------------------------------------------------------
+// Marketplace wrapper tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn generate_marketplace_wrapper() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("Marketplace.luau");
+
+    let mut lockfile = records_lockfile();
+    lockfile.products.insert(
+        "Coins100".into(),
+        ProductLock {
+            id: 333,
+            name: "100 Coins".into(),
+            price: 99,
+            description: None,
+            icon_asset_id: None,
+            icon_hash: None,
+            for_sale: true,
+            regional_pricing: false,
+            store_page: false,
+        },
+    );
+    let config = test_config_full(
+        CodegenStyle::Nested,
+        CodegenPaths {
+            products: Some("shop.items".into()),
+            ..Default::default()
+        },
+        BTreeMap::from([("passes.legacy".into(), 999)]),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+    );
+    let tree = build_tree(&lockfile, &config).unwrap();
+    generate_marketplace(&tree, &output).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.starts_with("--!strict\n"));
+    // Keys come from the config, not the generated path; extras are excluded
+    assert!(content.contains("export type PassKey = \"VIP\"\n"));
+    assert!(content.contains("export type ProductKey = \"Coins100\"\n"));
+    assert!(content.contains("local passIds: { [string]: number } = {\n\tVIP = 111,\n}"));
+    assert!(content.contains("\t[333] = \"Coins100\",\n"));
+    assert!(!content.contains("999"));
+    assert!(content.contains("function Marketplace.promptProduct(player: Player, key: ProductKey)"));
+    assert!(
+        content.contains("function Marketplace.ownsPass(player: Player, key: PassKey): boolean")
+    );
+    assert!(content.contains(
+        "function Marketplace.onProductPurchased(key: ProductKey, handler: ReceiptHandler)"
+    ));
+}
+
+#[test]
+fn generate_marketplace_without_products() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("Marketplace.luau");
+
+    generate_marketplace(&CodegenTree::new(), &output).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.contains("export type PassKey = never\n"));
+    assert!(content.contains("export type ProductKey = never\n"));
+}
+
+// ---------------------------------------------------------------------------
+// Strict Luau tests
+// ---------------------------------------------------------------------------
+
+fn strict_options() -> RenderOptions {
+    RenderOptions {
+        strict: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn generate_luau_strict_nested() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("GameIds.luau");
+
+    let tree = build_tree_default(&records_lockfile());
+    generate_luau_with(&tree, &output, &strict_options()).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.starts_with("--!strict\n"));
+    assert!(content.contains("export type PassKey = \"VIP\"\n"));
+    assert!(content.contains("export type BadgeKey = \"Welcome\"\n"));
+    assert!(content.contains("export type ProductKey = never\n"));
+    assert!(content.contains(
+        "export type GameIds = {\n\tbadges: {\n\t\tWelcome: number,\n\t},\n\tpasses: {\n\t\tVIP: number,\n\t},\n}\n"
+    ));
+    assert!(content.contains("local GameIds: GameIds = table.freeze({\n"));
+    assert!(content.contains("\tpasses = table.freeze({\n\t\tVIP = 111,\n\t}),\n"));
+    assert!(content.ends_with("})\n\nreturn GameIds\n"));
+}
+
+#[test]
+fn generate_luau_strict_flat() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("GameIds.luau");
+
+    let tree = build_tree_default_flat(&records_lockfile());
+    generate_luau_with(&tree, &output, &strict_options()).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.contains(
+        "export type GameIds = {\n\t[\"badges.Welcome\"]: number,\n\t[\"passes.VIP\"]: number,\n}\n"
+    ));
+    assert!(content.contains("\t[\"passes.VIP\"] = 111,\n"));
+}
+
+#[test]
+fn generate_luau_strict_records_and_reverse() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("GameIds.luau");
+
+    let tree = build_tree_default_flat(&records_lockfile());
+    let options = RenderOptions {
+        records: true,
+        reverse: Some(ReverseLookup::Key),
+        strict: true,
+    };
+    generate_luau_with(&tree, &output, &options).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.contains("\t[\"passes.VIP\"]: AssetRecord,\n"));
+    assert!(content.contains("\tbyId: { [number]: string },\n}\n"));
+    assert!(content.contains("\t[\"passes.VIP\"] = table.freeze({\n\t\tid = 111,\n"));
+    assert!(content.contains("\tbyId = table.freeze({\n\t\t[111] = \"VIP\",\n"));
+}
+
+// ---------------------------------------------------------------------------
+// roblox-ts tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn generate_typescript_default_output_is_plain() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("GameIds.d.ts");
+
+    let tree = build_tree_default_flat(&records_lockfile());
+    generate_typescript(&tree, &output).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    // Key unions are only part of the roblox-ts package
+    assert_eq!(
+        content,
+        "// This file is auto-generated by rbxsync. Do not edit manually.\n\n\
+         declare const GameIds: {\n\t\"badges.Welcome\": number\n\t\"passes.VIP\": number\n}\n\n\
+         export = GameIds\n"
+    );
+}
+
+#[test]
+fn generate_roblox_ts_package() {
+    let dir = tempfile::tempdir().unwrap();
+    let package = dir.path().join("shared").join("GameIds");
+
+    let tree = build_tree_default(&records_lockfile());
+    let options = RenderOptions {
+        records: true,
+        ..Default::default()
+    };
+    let written = generate_roblox_ts(&tree, &package, &options).unwrap();
+    assert_eq!(
+        written,
+        vec![package.join("init.luau"), package.join("index.d.ts")]
+    );
+
+    let luau = std::fs::read_to_string(package.join("init.luau")).unwrap();
+    assert!(luau.contains("local GameIds: GameIds = {\n"));
+    assert!(luau.ends_with("return { GameIds = GameIds }\n"));
+
+    let ts = std::fs::read_to_string(package.join("index.d.ts")).unwrap();
+    assert!(ts.contains("export type PassKey = \"VIP\"\n"));
+    assert!(ts.contains("export declare const enum PassId {\n\tVIP = 111,\n}\n"));
+    assert!(ts.contains("export declare const enum BadgeId {\n\tWelcome = 222,\n}\n"));
+    assert!(ts.contains("export declare const enum ProductId {\n}\n"));
+    assert!(ts.contains("export interface AssetRecord {\n"));
+    assert!(ts.contains("export declare const GameIds: {\n\tbadges: {\n\t\tWelcome: AssetRecord\n"));
+    assert!(!ts.contains("export ="));
+}
+
+fn numeric_products_lockfile(keys: &[&str]) -> Lockfile {
+    let mut lockfile = Lockfile::default();
+    for (i, key) in keys.iter().enumerate() {
+        lockfile.products.insert(
+            key.to_string(),
+            ProductLock {
+                id: 500 + i as u64,
+                name: key.to_string(),
+                price: 99,
+                description: None,
+                icon_asset_id: None,
+                icon_hash: None,
+                for_sale: true,
+                regional_pricing: false,
+                store_page: false,
+            },
+        );
+    }
+    lockfile
+}
+
+#[test]
+fn roblox_ts_enum_prefixes_numeric_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let package = dir.path().join("GameIds");
+
+    let tree = build_tree_default(&numeric_products_lockfile(&[
+        "100", "250Gems", "1.5", "Coins",
+    ]));
+    generate_roblox_ts(&tree, &package, &RenderOptions::default()).unwrap();
+    let ts = std::fs::read_to_string(package.join("index.d.ts")).unwrap();
+
+    assert!(ts.contains(
+        "export declare const enum ProductId {\n\t\"_1.5\" = 502,\n\t_100 = 500,\n\t_250Gems = 501,\n\tCoins = 503,\n}\n"
+    ));
+    // The key union still uses the config keys
+    assert!(ts.contains("export type ProductKey = \"1.5\" | \"100\" | \"250Gems\" | \"Coins\"\n"));
+}
+
+#[test]
+fn roblox_ts_enum_member_collision_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let package = dir.path().join("GameIds");
+
+    let tree = build_tree_default(&numeric_products_lockfile(&["100", "_100"]));
+    let err = generate_roblox_ts(&tree, &package, &RenderOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("'100' and '_100'"), "{err}");
+}
+
+#[test]
+fn typescript_output_overrides_default_location() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = test_config(
+        CodegenPaths::default(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+    );
+    config.codegen.output = Some("src/GameIds.luau".into());
+    config.codegen.typescript_output = Some("types/GameIds.d.ts".into());
+    config.codegen.roblox_ts = Some("pkg/GameIds".into());
+
+    let written = generate(&records_lockfile(), &config, dir.path()).unwrap();
+    assert_eq!(
+        written,
+        vec![
+            dir.path().join("src/GameIds.luau"),
+            dir.path().join("types/GameIds.d.ts"),
+            dir.path().join("pkg/GameIds/init.luau"),
+            dir.path().join("pkg/GameIds/index.d.ts"),
+        ]
+    );
+    assert!(!dir.path().join("src/GameIds.d.ts").exists());
+}
+
+// ---------------------------------------------------------------------------
+// Manifest tests
+// ---------------------------------------------------------------------------
+
+fn manifest_config() -> Config {
+    test_config_full(
+        CodegenStyle::Flat,
+        CodegenPaths::default(),
+        BTreeMap::from([("passes.legacy".into(), 999)]),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+    )
+}
+
+#[test]
+fn generate_json_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("catalog.json");
+
+    let mut lockfile = records_lockfile();
+    lockfile.universe_id = 42;
+    generate_manifest(&lockfile, &manifest_config(), &output).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+    let manifest: Manifest = serde_json::from_str(&content).unwrap();
+
+    assert_eq!(manifest.version, MANIFEST_VERSION);
+    assert_eq!(manifest.universe_id, 42);
+    assert_eq!(manifest.resources.len(), 2);
+
+    let vip = &manifest.resources[0];
+    assert_eq!(vip.key, "VIP");
+    assert_eq!(vip.resource_type, ManifestResourceType::Pass);
+    assert_eq!(vip.id, 111);
+    assert_eq!(vip.price, Some(499));
+    assert_eq!(vip.for_sale, Some(true));
+    assert_eq!(vip.icon_asset_id, Some(4242));
+
+    let welcome = &manifest.resources[1];
+    assert_eq!(welcome.resource_type, ManifestResourceType::Badge);
+    assert_eq!(welcome.price, None);
+    assert_eq!(welcome.enabled, Some(true));
+
+    assert_eq!(manifest.extra.len(), 1);
+    assert_eq!(manifest.extra[0].key, "passes.legacy");
+    assert_eq!(manifest.extra[0].id, 999);
+
+    // Inapplicable fields are present as null so the schema is uniform
+    assert!(content.contains("\"price\": null"));
+    assert!(content.contains("\"type\": \"badge\""));
+}
+
+#[test]
+fn generate_yaml_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("catalog.yaml");
+
+    generate_manifest(&records_lockfile(), &manifest_config(), &output).unwrap();
+    let content = std::fs::read_to_string(&output).unwrap();
+
+    assert!(content.starts_with("version: 1\n"));
+    assert!(content.contains("type: pass"));
+    let manifest: Manifest = serde_yaml::from_str(&content).unwrap();
+    assert_eq!(manifest.resources.len(), 2);
+}
+
+// ---------------------------------------------------------------------------
+// Template tests
+// ---------------------------------------------------------------------------
+
+const CATALOG_TEMPLATE: &str = r#"local Catalog = require(script.Parent.Catalog)
+
+return Catalog.new({
+{% for entry in passes %}
+	{{ entry.key | luau_key }} = { id = {{ entry.id }}, name = {{ entry.name | luau_string }}{% if entry.price is not none %}, price = {{ entry.price }}{% endif %} },
+{% endfor %}
+})
+"#;
+
+#[test]
+fn render_template_loops_and_escapes() {
+    let mut lockfile = records_lockfile();
+    lockfile.passes.insert(
+        "my-pass".into(),
+        PassLock {
+            id: 333,
+            name: "My Pass".into(),
+            price: None,
+            description: None,
+            icon_asset_id: None,
+            icon_hash: None,
+            for_sale: false,
+            regional_pricing: false,
+        },
+    );
+    let tree = build_tree_default_flat(&lockfile);
+    let content = render_template(CATALOG_TEMPLATE, &tree, &lockfile, "Catalog").unwrap();
+
+    assert_eq!(
+        content,
+        "local Catalog = require(script.Parent.Catalog)\n\nreturn Catalog.new({\n\
+         \tVIP = { id = 111, name = \"VIP \\\"Gold\\\"\", price = 499 },\n\
+         \t[\"my-pass\"] = { id = 333, name = \"My Pass\" },\n\
+         })\n"
+    );
+}
+
+#[test]
+fn render_template_recurses_tree() {
+    let source = r#"{% macro walk(nodes) %}
+{% for node in nodes %}
+{% if node.entry %}
+{{ node.path }}={{ node.entry.id }}
+{% else %}
+{{ walk(node.children) }}
+{%- endif %}
+{% endfor %}
+{% endmacro %}
+{{ name }}:{{ universe_id }}
+{{ walk(tree) }}"#;
+
+    let tree = build_tree_default(&records_lockfile());
+    let content = render_template(source, &tree, &records_lockfile(), "GameIds").unwrap();
+
+    assert!(content.starts_with("GameIds:0\n"));
+    assert!(content.contains("badges.Welcome=222\n"));
+    assert!(content.contains("passes.VIP=111\n"));
+}
+
+#[test]
+fn render_template_keys_follow_key_case() {
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[codegen]
+key_case = "snake"
+style = "nested"
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    let tree = build_tree(&records_lockfile(), &config).unwrap();
+    let source = "{% for entry in passes %}{{ entry.key }}/{{ entry.config_key }}{% endfor %}";
+    let content = render_template(source, &tree, &records_lockfile(), "GameIds").unwrap();
+
+    assert_eq!(content, "vip/VIP");
+}
+
+#[test]
+fn render_template_rejects_undefined_variables() {
+    let tree = build_tree_default(&records_lockfile());
+    let err = render_template("{{ pases }}", &tree, &records_lockfile(), "GameIds");
+    assert!(err.is_err());
+}
+
+#[test]
+fn generate_template_from_config() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("templates")).unwrap();
+    std::fs::write(
+        dir.path().join("templates/ids.ts.jinja"),
+        "{% for e in entries %}export const {{ e.key }} = {{ e.id }}\n{% endfor %}",
+    )
+    .unwrap();
+
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[[codegen.templates]]
+template = "templates/ids.ts.jinja"
+output = "out/ids.ts"
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    let written = generate(&records_lockfile(), &config, dir.path()).unwrap();
+    assert_eq!(written, vec![dir.path().join("out/ids.ts")]);
+
+    let content = std::fs::read_to_string(dir.path().join("out/ids.ts")).unwrap();
+    assert_eq!(
+        content,
+        "export const Welcome = 222\nexport const VIP = 111\n"
+    );
+
+    let tree = build_tree(&records_lockfile(), &config).unwrap();
+    let missing = generate_template(
+        &tree,
+        &records_lockfile(),
+        &dir.path().join("templates/missing.jinja"),
+        &dir.path().join("out/missing.ts"),
+    );
+    assert!(missing.is_err());
+}
+
+// ---------------------------------------------------------------------------
+// Multiple output tests
+// ---------------------------------------------------------------------------
+
+const OUTPUTS_CONFIG: &str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[codegen]
+output = "src/server/GameIds.luau"
+
+[codegen.extra]
+"passes.legacy" = 999
+
+[[codegen.outputs]]
+path = "src/client/ClientIds.luau"
+include = ["passes", "products"]
+exclude = ["tag:internal"]
+
+[[codegen.outputs]]
+path = "packages/shop/ShopIds.luau"
+style = "nested"
+include = ["tag:shop"]
+
+[codegen.outputs.paths]
+products = "items"
+
+[[codegen.outputs]]
+path = "types/Ids.d.ts"
+language = "typescript"
+exclude = ["extra"]
+
+[passes.VIP]
+tags = ["shop"]
+
+[passes.Debug]
+tags = ["internal"]
+
+[badges.Welcome]
+
+[products.Coins]
+price = 10
+tags = ["shop"]
+"#;
+
+fn outputs_lockfile() -> Lockfile {
+    let mut lockfile = records_lockfile();
+    lockfile.passes.insert(
+        "Debug".into(),
+        PassLock {
+            id: 444,
+            name: "Debug".into(),
+            price: None,
+            description: None,
+            icon_asset_id: None,
+            icon_hash: None,
+            for_sale: false,
+            regional_pricing: false,
+        },
+    );
+    lockfile.products.insert(
+        "Coins".into(),
+        ProductLock {
+            id: 333,
+            name: "Coins".into(),
+            price: 10,
+            description: None,
+            icon_asset_id: None,
+            icon_hash: None,
+            for_sale: true,
+            regional_pricing: false,
+            store_page: false,
+        },
+    );
+    lockfile
+}
+
+#[test]
+fn generate_multiple_outputs() {
+    let dir = tempfile::tempdir().unwrap();
+    let config: Config = toml::from_str(OUTPUTS_CONFIG).unwrap();
+
+    let written = generate(&outputs_lockfile(), &config, dir.path()).unwrap();
+    assert_eq!(
+        written,
+        vec![
+            dir.path().join("src/server/GameIds.luau"),
+            dir.path().join("src/client/ClientIds.luau"),
+            dir.path().join("packages/shop/ShopIds.luau"),
+            dir.path().join("types/Ids.d.ts"),
+        ]
+    );
+
+    let server = std::fs::read_to_string(&written[0]).unwrap();
+    for expected in [
+        "passes.Debug",
+        "passes.VIP",
+        "badges.Welcome",
+        "products.Coins",
+        "passes.legacy",
+    ] {
+        assert!(server.contains(expected), "server missing {expected}");
+    }
+
+    let client = std::fs::read_to_string(&written[1]).unwrap();
+    assert!(client.contains("local ClientIds = {"));
+    assert!(client.contains(r#"["passes.VIP"] = 111,"#));
+    assert!(client.contains(r#"["products.Coins"] = 333,"#));
+    assert!(!client.contains("Debug"));
+    assert!(!client.contains("Welcome"));
+    assert!(!client.contains("legacy"));
+
+    let shop = std::fs::read_to_string(&written[2]).unwrap();
+    assert!(shop.contains("\titems = {\n\t\tCoins = 333,\n\t},"));
+    assert!(shop.contains("\tpasses = {\n\t\tVIP = 111,\n\t},"));
+    assert!(!shop.contains("Welcome"));
+
+    let types = std::fs::read_to_string(&written[3]).unwrap();
+    assert!(types.contains("declare const Ids: {"));
+    assert!(types.contains(r#""passes.Debug": number"#));
+    assert!(!types.contains("legacy"));
+}
+
+#[test]
+fn output_filters_reject_unknown_values() {
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[[codegen.outputs]]
+path = "Ids.luau"
+include = ["gamepasses"]
+"#;
+    let err = toml::from_str::<Config>(toml_str).unwrap_err().to_string();
+    assert!(err.contains("invalid filter 'gamepasses'"));
+}
+
+fn codegen_cli(dir: &std::path::Path, select: &str) -> Cli {
+    let config_path = dir.join("rbxsync.toml");
+    std::fs::write(&config_path, OUTPUTS_CONFIG).unwrap();
+    let mut lockfile = outputs_lockfile();
+    lockfile.version = 1;
+    lockfile.save(&dir.join(LOCKFILE_NAME)).unwrap();
+
+    Cli {
+        command: Commands::Codegen { check: false },
+        config: config_path,
+        api_key: None,
+        select: vec![select.into()],
+    }
+}
+
+#[test]
+fn codegen_select_narrows_only_extra_outputs() {
+    let dir = tempfile::tempdir().unwrap();
+    let cli = codegen_cli(dir.path(), "type:products");
+    rbxsync::commands::codegen::run(&cli, false).unwrap();
+
+    // The main output keeps every resource
+    let main = std::fs::read_to_string(dir.path().join("src/server/GameIds.luau")).unwrap();
+    assert!(main.contains(r#"["passes.VIP"] = 111,"#));
+    assert!(main.contains(r#"["badges.Welcome"] = 222,"#));
+    assert!(main.contains(r#"["products.Coins"] = 333,"#));
+    assert!(main.contains(r#"["passes.legacy"] = 999,"#));
+
+    // Outputs apply --select on top of their own filters
+    let client = std::fs::read_to_string(dir.path().join("src/client/ClientIds.luau")).unwrap();
+    assert!(client.contains(r#"["products.Coins"] = 333,"#));
+    assert!(!client.contains("VIP"));
+    let shop = std::fs::read_to_string(dir.path().join("packages/shop/ShopIds.luau")).unwrap();
+    assert!(shop.contains("Coins = 333,"));
+    assert!(!shop.contains("VIP"));
+}
+
+#[test]
+fn codegen_select_rejects_check() {
+    let dir = tempfile::tempdir().unwrap();
+    let cli = codegen_cli(dir.path(), "tag:shop");
+    let err = rbxsync::commands::codegen::run(&cli, true).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("--select can't be combined with --check"));
+}
+
+#[test]
+fn output_select_expression() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config: Config = toml::from_str(OUTPUTS_CONFIG).unwrap();
+    let mut output: CodegenOutput = toml::from_str(
+        r#"
+path = "Selected.luau"
+select = "tag:shop,!type:products | passes.leg*"
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        output.select.as_ref().unwrap().to_string(),
+        "tag:shop,!type:products | key:passes.leg*"
+    );
+    output.include.clear();
+    config.codegen.outputs = vec![output];
+
+    let written = generate(&outputs_lockfile(), &config, dir.path()).unwrap();
+    let selected = std::fs::read_to_string(&written[1]).unwrap();
+    assert!(selected.contains(r#"["passes.VIP"] = 111,"#));
+    assert!(selected.contains(r#"["passes.legacy"] = 999,"#));
+    assert!(!selected.contains("Coins"));
+    assert!(!selected.contains("Debug"));
+    assert!(!selected.contains("Welcome"));
+}
+
+#[test]
+fn output_language_roblox_ts() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[[codegen.outputs]]
+path = "pkg/GameIds"
+language = "roblox-ts"
+include = ["badges"]
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    let written = generate(&records_lockfile(), &config, dir.path()).unwrap();
+    assert_eq!(
+        written,
+        vec![
+            dir.path().join("pkg/GameIds/init.luau"),
+            dir.path().join("pkg/GameIds/index.d.ts"),
+        ]
+    );
+
+    let ts = std::fs::read_to_string(&written[1]).unwrap();
+    assert!(ts.contains("export type PassKey = never"));
+    assert!(ts.contains("export type BadgeKey = \"Welcome\""));
+}
+
+// ---------------------------------------------------------------------------
+// Key case tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn transform_key_cases() {
+    let cases = [
+        (
+            "vip_pass",
+            ["vip_pass", "vipPass", "VipPass", "vip_pass", "VIP_PASS"],
+        ),
+        (
+            "VIPPass",
+            ["VIPPass", "vipPass", "VipPass", "vip_pass", "VIP_PASS"],
+        ),
+        (
+            "coins100",
+            ["coins100", "coins100", "Coins100", "coins100", "COINS100"],
+        ),
+        (
+            "Coins100Pack",
+            [
+                "Coins100Pack",
+                "coins100Pack",
+                "Coins100Pack",
+                "coins100_pack",
+                "COINS100_PACK",
+            ],
+        ),
+        (
+            "double-xp boost",
+            [
+                "double-xp boost",
+                "doubleXpBoost",
+                "DoubleXpBoost",
+                "double_xp_boost",
+                "DOUBLE_XP_BOOST",
+            ],
+        ),
+    ];
+    let modes = [
+        KeyCase::Preserve,
+        KeyCase::Camel,
+        KeyCase::Pascal,
+        KeyCase::Snake,
+        KeyCase::ScreamingSnake,
+    ];
+    for (key, expected) in cases {
+        for (mode, want) in modes.iter().zip(expected) {
+            assert_eq!(transform_key(key, *mode), want, "{key} as {mode:?}");
+        }
+    }
+}
+
+#[test]
+fn key_case_applies_to_leaf_keys_only() {
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[codegen]
+key_case = "camel"
+
+[codegen.paths]
+badges = "Player_Badges"
+
+[codegen.extra]
+"places.start_place" = 42
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    let tree = build_tree(&records_lockfile(), &config).unwrap();
+    let keys: Vec<&str> = tree.keys().map(String::as_str).collect();
+    assert_eq!(
+        keys,
+        vec!["Player_Badges.welcome", "passes.vip", "places.startPlace"]
+    );
+}
+
+#[test]
+fn key_case_collision_is_an_error() {
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[codegen]
+key_case = "pascal"
+style = "nested"
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    let mut lockfile = records_lockfile();
+    let mut lock = lockfile.passes["VIP"].clone();
+    lock.id = 999;
+    lockfile.passes.insert("vip".to_string(), lock);
+
+    let err = build_tree(&lockfile, &config).unwrap_err().to_string();
+    assert_eq!(
+        err,
+        "Codegen path collision: pass 'VIP' and pass 'vip' are both generated at 'passes.Vip'"
+    );
+
+    // Distinct keys under `preserve` don't collide.
+    let config: Config = toml::from_str(&toml_str.replace("pascal", "preserve")).unwrap();
+    assert!(build_tree(&lockfile, &config).is_ok());
+}
+
+#[test]
+fn strict_key_types_use_transformed_keys() {
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[codegen]
+key_case = "pascal"
+style = "nested"
+strict = true
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    let mut lockfile = records_lockfile();
+    let lock = lockfile.passes.remove("VIP").unwrap();
+    lockfile.passes.insert("vip_pass".to_string(), lock);
+
+    let tree = build_tree(&lockfile, &config).unwrap();
+    let content = render_luau(&tree, "GameIds", &RenderOptions::from(&config.codegen)).unwrap();
+
+    assert!(content.contains("export type PassKey = \"VipPass\"\n"));
+    assert!(content.contains("\tpasses: {\n\t\tVipPass: number,\n"));
+    assert!(content.contains("\t\tVipPass = 111,\n"));
+    assert!(!content.contains("vip_pass"));
+}
+
+#[test]
+fn output_key_case_overrides_codegen() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[codegen]
+output = "GameIds.luau"
+key_case = "snake"
+
+[[codegen.outputs]]
+path = "ClientIds.luau"
+key_case = "screaming_snake"
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    generate(&records_lockfile(), &config, dir.path()).unwrap();
+
+    let main = std::fs::read_to_string(dir.path().join("GameIds.luau")).unwrap();
+    assert!(main.contains("[\"passes.vip\"] = 111"));
+    let client = std::fs::read_to_string(dir.path().join("ClientIds.luau")).unwrap();
+    assert!(client.contains("[\"passes.VIP\"] = 111"));
+    assert!(client.contains("[\"badges.WELCOME\"] = 222"));
+}
+
+// ---------------------------------------------------------------------------
+// Path collision tests
+// ---------------------------------------------------------------------------
+
+const COLLISION_CONFIG: &str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+"#;
+
+#[test]
+fn extra_colliding_with_resource_is_an_error() {
+    for style in ["flat", "nested"] {
+        let toml_str = format!(
+            "{COLLISION_CONFIG}\n[codegen]\nstyle = \"{style}\"\n\n[codegen.extra]\n\"passes.VIP\" = 5\n"
+        );
+        let config: Config = toml::from_str(&toml_str).unwrap();
+        let err = build_tree(&records_lockfile(), &config)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "Codegen path collision: pass 'VIP' and codegen.extra 'passes.VIP' are both generated at 'passes.VIP'",
+            "{style}"
+        );
+    }
+}
+
+#[test]
+fn leaf_and_branch_collision_is_an_error() {
+    // The leaf is inserted first: extra entries nest under an existing ID.
+    let toml_str = format!(
+        "{COLLISION_CONFIG}\n[codegen]\nstyle = \"nested\"\n\n[codegen.extra]\n\"passes.VIP.gold\" = 5\n"
+    );
+    let config: Config = toml::from_str(&toml_str).unwrap();
+    let err = build_tree(&records_lockfile(), &config)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        err,
+        "Codegen path collision: pass 'VIP' is generated at 'passes.VIP', but codegen.extra 'passes.VIP.gold' needs 'passes.VIP' to be a table"
+    );
+
+    // The branch is inserted first: a section path points at a table of passes.
+    let toml_str = format!(
+        "{COLLISION_CONFIG}\n[codegen]\nstyle = \"nested\"\n\n[codegen.paths]\nbadges = \"shop\"\npasses = \"shop.Welcome\"\n"
+    );
+    let config: Config = toml::from_str(&toml_str).unwrap();
+    let err = build_tree(&records_lockfile(), &config)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        err,
+        "Codegen path collision: badge 'Welcome' is generated at 'shop.Welcome', but pass 'VIP' needs 'shop.Welcome' to be a table"
+    );
+
+    // Flat keys don't nest, so the same layout is fine there.
+    let config: Config = toml::from_str(&toml_str.replace("nested", "flat")).unwrap();
+    assert!(build_tree(&records_lockfile(), &config).is_ok());
+}
+
+#[test]
+fn path_collisions_include_unsynced_items_and_outputs() {
+    let toml_str = format!(
+        r#"{COLLISION_CONFIG}
+[codegen]
+output = "GameIds.luau"
+
+[[codegen.outputs]]
+path = "Shop.luau"
+key_case = "snake"
+
+[passes.VIP]
+price = 499
+
+[passes.vip]
+price = 99
+path = "passes"
+
+[products.Gems]
+price = 10
+path = "passes"
+"#
+    );
+    let config: Config = toml::from_str(&toml_str).unwrap();
+
+    // Only VIP is in the lockfile; `vip` and `Gems` haven't been created yet.
+    let errors: Vec<String> = path_collisions(&records_lockfile(), &config)
+        .iter()
+        .map(|e| format!("{e:#}"))
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            "In codegen output Shop.luau: Codegen path collision: pass 'VIP' and pass 'vip' are both generated at 'passes.vip'".to_string(),
+        ]
+    );
+
+    let mut lockfile = records_lockfile();
+    lockfile.passes.remove("VIP");
+    assert_eq!(path_collisions(&lockfile, &config).len(), 1);
+    assert!(build_tree(&lockfile, &config).is_ok());
+}
+
+// ---------------------------------------------------------------------------
+// Render tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn render_matches_generate_without_writing() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[codegen]
+output = "src/GameIds.luau"
+typescript = true
+roblox_ts = "pkg/GameIds"
+marketplace = "src/Marketplace.luau"
+manifest = "catalog.yaml"
+
+[[codegen.outputs]]
+path = "client/ClientIds.luau"
+include = ["passes"]
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    let lockfile = records_lockfile();
+
+    let rendered = render(&lockfile, &config, dir.path()).unwrap();
+    assert!(std::fs::read_dir(dir.path()).unwrap().next().is_none());
+
+    let written = generate(&lockfile, &config, dir.path()).unwrap();
+    let paths: Vec<_> = rendered.iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(paths, written);
+    assert_eq!(written.len(), 7);
+    for (path, content) in rendered {
+        assert_eq!(std::fs::read(&path).unwrap(), content);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Model tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn model_rbxmx_wraps_module_source() {
+    let dir = tempfile::tempdir().unwrap();
+    let tree = build_tree_default(&records_lockfile());
+    let path = dir.path().join("GameIds.rbxmx");
+    generate_model(&tree, &path, &RenderOptions::default()).unwrap();
+
+    let source = render_luau(&tree, "GameIds", &RenderOptions::default()).unwrap();
+    let xml = std::fs::read_to_string(&path).unwrap();
+    assert!(xml.starts_with("<roblox version=\"4\">\n"));
+    assert!(xml.contains("<Item class=\"ModuleScript\" referent=\"RBX0\">"));
+    assert!(xml.contains("<string name=\"Name\">GameIds</string>"));
+    assert!(xml.contains(&format!(
+        "<ProtectedString name=\"Source\"><![CDATA[{source}]]></ProtectedString>"
+    )));
+
+    let escaped = rbxsync::codegen::model::render_rbxmx("A&B", "x = t[a[1]]>0");
+    assert!(escaped.contains("<string name=\"Name\">A&amp;B</string>"));
+    assert!(escaped.contains("<![CDATA[x = t[a[1]]]]><![CDATA[>0]]>"));
+}
+
+#[test]
+fn model_rbxm_chunks() {
+    let dir = tempfile::tempdir().unwrap();
+    let tree = build_tree_default(&records_lockfile());
+    let path = dir.path().join("GameIds.rbxm");
+    generate_model(&tree, &path, &RenderOptions::default()).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    let source = render_luau(&tree, "GameIds", &RenderOptions::default()).unwrap();
+
+    assert_eq!(&bytes[..14], b"<roblox!\x89\xff\x0d\x0a\x1a\x0a");
+    assert_eq!(&bytes[14..16], &[0, 0]); // version
+    assert_eq!(&bytes[16..24], &[1, 0, 0, 0, 1, 0, 0, 0]); // classes, instances
+
+    let u32_at = |b: &[u8], i: usize| u32::from_le_bytes(b[i..i + 4].try_into().unwrap());
+    let mut chunks = Vec::new();
+    let mut pos = 32;
+    while pos < bytes.len() {
+        let name = String::from_utf8(bytes[pos..pos + 4].to_vec()).unwrap();
+        assert_eq!(u32_at(&bytes, pos + 4), 0, "{name} is stored uncompressed");
+        let len = u32_at(&bytes, pos + 8) as usize;
+        chunks.push((name, bytes[pos + 16..pos + 16 + len].to_vec()));
+        pos += 16 + len;
+    }
+    assert_eq!(pos, bytes.len());
+
+    let names: Vec<&str> = chunks.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(names, vec!["INST", "PROP", "PROP", "PRNT", "END\0"]);
+
+    let inst = &chunks[0].1;
+    assert_eq!(&inst[4..20], b"\x0c\0\0\0ModuleScript");
+    assert_eq!(&inst[20..], &[0, 1, 0, 0, 0, 0, 0, 0, 0]);
+
+    let prop = |data: &[u8]| -> (String, u8, String) {
+        let name_len = u32_at(data, 4) as usize;
+        let name = String::from_utf8(data[8..8 + name_len].to_vec()).unwrap();
+        let ty = data[8 + name_len];
+        let value_len = u32_at(data, 9 + name_len) as usize;
+        let value = String::from_utf8(data[13 + name_len..13 + name_len + value_len].to_vec());
+        (name, ty, value.unwrap())
+    };
+    assert_eq!(prop(&chunks[1].1), ("Name".into(), 1, "GameIds".into()));
+    assert_eq!(prop(&chunks[2].1), ("Source".into(), 1, source));
+
+    // One child (referent 0) whose parent is -1 (zigzag-encoded as 1).
+    assert_eq!(chunks[3].1, vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(chunks[4].1, b"</roblox>");
+
+    let err = generate_model(
+        &tree,
+        &dir.path().join("GameIds.lua"),
+        &RenderOptions::default(),
+    );
+    assert!(err
+        .unwrap_err()
+        .to_string()
+        .contains("must end in .rbxm or .rbxmx"));
+}
+
+// ---------------------------------------------------------------------------
+// Rojo project tests
+// ---------------------------------------------------------------------------
+
+const ROJO_PROJECT: &str = r#"{
+  "name": "game",
+  "tree": {
+    "$className": "DataModel",
+    "ReplicatedStorage": {
+      "Shared": { "$path": "src/shared" },
+      "Packages": { "$path": { "optional": "Packages" } }
+    },
+    "ServerScriptService": {
+      "Marketplace": { "$path": "src/server/Marketplace.luau" }
+    }
+  }
+}"#;
+
+fn rojo_dir() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("default.project.json"), ROJO_PROJECT).unwrap();
+    dir
+}
+
+fn rojo_config(codegen: &str) -> Config {
+    let toml_str = format!(
+        "[experience]\nuniverse_id = 1\n\n[experience.creator]\ntype = \"user\"\nid = 1\n\n[codegen]\n{codegen}"
+    );
+    toml::from_str(&toml_str).unwrap()
+}
+
+#[test]
+fn rojo_project_maps_files_and_instances() {
+    let dir = rojo_dir();
+    let project = RojoProject::load(&dir.path().join("default.project.json")).unwrap();
+    let root = dir.path();
+
+    assert_eq!(
+        project
+            .instance_for_file(&root.join("src/shared/ids/GameIds.luau"))
+            .as_deref(),
+        Some("ReplicatedStorage.Shared.ids.GameIds")
+    );
+    assert_eq!(
+        project
+            .instance_for_file(&root.join("./src/shared/ids/init.luau"))
+            .as_deref(),
+        Some("ReplicatedStorage.Shared.ids")
+    );
+    assert_eq!(
+        project
+            .instance_for_file(&root.join("src/server/Marketplace.luau"))
+            .as_deref(),
+        Some("ServerScriptService.Marketplace")
+    );
+    assert_eq!(
+        project.instance_for_file(&root.join("src/GameIds.luau")),
+        None
+    );
+
+    assert_eq!(
+        project
+            .file_for_instance("ReplicatedStorage.Shared.Data.GameIds")
+            .unwrap(),
+        root.join("src/shared/Data/GameIds.luau")
+    );
+    assert_eq!(
+        project
+            .file_for_instance("ServerScriptService.Marketplace")
+            .unwrap(),
+        root.join("src/server/Marketplace.luau")
+    );
+    let err = project
+        .file_for_instance("Workspace.GameIds")
+        .unwrap_err()
+        .to_string();
+    assert!(err.starts_with("Workspace.GameIds isn't inside any $path in Rojo project"));
+}
+
+#[test]
+fn rojo_output_instance_derives_output() {
+    let dir = rojo_dir();
+    let config =
+        rojo_config("output_instance = \"ReplicatedStorage.Shared.GameIds\"\ntypescript = true\n");
+    let written = generate(&records_lockfile(), &config, dir.path()).unwrap();
+    assert_eq!(
+        written,
+        vec![
+            dir.path().join("src/shared/GameIds.luau"),
+            dir.path().join("src/shared/GameIds.d.ts"),
+        ]
+    );
+    let luau = std::fs::read_to_string(&written[0]).unwrap();
+    assert!(luau.contains("local GameIds = {"));
+
+    let both = rojo_config(
+        "output = \"a.luau\"\noutput_instance = \"ReplicatedStorage.Shared.GameIds\"\n",
+    );
+    assert!(resolve_output(&both, dir.path()).is_err());
+
+    let no_project = tempfile::tempdir().unwrap();
+    let err = resolve_output(&config, no_project.path())
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("needs a Rojo project"));
+}
+
+#[test]
+fn rojo_warns_about_unsynced_outputs() {
+    let dir = rojo_dir();
+    let config = rojo_config(
+        "output = \"src/GameIds.luau\"\nmarketplace = \"src/server/Marketplace.luau\"\n\n[[codegen.outputs]]\npath = \"src/shared/Client.luau\"\n\n[[codegen.outputs]]\npath = \"types/GameIds.d.ts\"\nlanguage = \"typescript\"\n",
+    );
+    let warnings = rojo_warnings(&config, dir.path()).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(
+        warnings[0].starts_with("codegen.output src/GameIds.luau isn't inside any path synced by")
+    );
+
+    // Without a project there is nothing to check against.
+    let no_project = tempfile::tempdir().unwrap();
+    assert!(rojo_warnings(&config, no_project.path())
+        .unwrap()
+        .is_empty());
+
+    // An explicit project must exist.
+    let missing =
+        rojo_config("output = \"src/GameIds.luau\"\nrojo_project = \"game.project.json\"\n");
+    assert!(rojo_warnings(&missing, dir.path()).is_err());
+}
+
+// ---------------------------------------------------------------------------
+// Data tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn data_is_emitted_into_records_with_inferred_types() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml_str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[codegen]
+output = "GameIds.luau"
+typescript = true
+records = true
+strict = true
+
+[passes.VIP]
+price = 499
+data = { tier = "gold", perks = ["chat", 2], bonus = { "xp-rate" = 1.5 } }
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    generate(&records_lockfile(), &config, dir.path()).unwrap();
+
+    let luau = std::fs::read_to_string(dir.path().join("GameIds.luau")).unwrap();
+    assert!(luau.contains(
+        "\t[\"passes.VIP\"]: AssetRecord & { data: { bonus: { [\"xp-rate\"]: number }, perks: { number | string }, tier: string } },\n"
+    ));
+    assert!(luau.contains(
+        "\t\tdata = table.freeze({ bonus = table.freeze({ [\"xp-rate\"] = 1.5 }), perks = table.freeze({ \"chat\", 2 }), tier = \"gold\" }),\n"
+    ));
+    // Entries without data keep the plain record type.
+    assert!(luau.contains("\t[\"badges.Welcome\"]: AssetRecord,\n"));
+
+    let ts = std::fs::read_to_string(dir.path().join("GameIds.d.ts")).unwrap();
+    assert!(ts.contains(
+        "\t\"passes.VIP\": GameIds.AssetRecord & { readonly data: { readonly bonus: { readonly \"xp-rate\": number }; readonly perks: readonly (number | string)[]; readonly tier: string } }\n"
+    ));
+
+    // Without records, entries stay bare IDs.
+    let config: Config = toml::from_str(&toml_str.replace("records = true", "")).unwrap();
+    generate(&records_lockfile(), &config, dir.path()).unwrap();
+    let luau = std::fs::read_to_string(dir.path().join("GameIds.luau")).unwrap();
+    assert!(!luau.contains("data"));
+}
@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 use rbxsync::config::Config;
 
@@ -158,6 +159,7 @@ fn skip_serializing_empty_maps() {
         },
         codegen: Default::default(),
         icons: Default::default(),
+        guardrails: Default::default(),
         passes: BTreeMap::new(),
         badges: BTreeMap::new(),
         products: BTreeMap::new(),
@@ -317,3 +319,89 @@ fn resolve_name_helper() {
     assert_eq!(resolve_name(Some("VIP Pass"), "vip"), "VIP Pass");
     assert_eq!(resolve_name(None, "vip"), "vip");
 }
+
+#[test]
+fn auto_icons_resolve_by_convention() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_dir = dir.path();
+
+    std::fs::create_dir_all(config_dir.join("icons/passes")).unwrap();
+    std::fs::create_dir_all(config_dir.join("icons/badges")).unwrap();
+    std::fs::write(config_dir.join("icons/passes/VIP.png"), b"fake").unwrap();
+    std::fs::write(config_dir.join("icons/badges/Welcome.webp"), b"fake").unwrap();
+    std::fs::write(config_dir.join("explicit.png"), b"fake").unwrap();
+
+    let path = config_dir.join("rbxsync.toml");
+    std::fs::write(
+        &path,
+        r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[icons]
+auto = true
+
+[passes.VIP]
+price = 100
+
+[passes.Explicit]
+icon = "explicit.png"
+
+[badges.Welcome]
+
+[products.Coins]
+price = 10
+"#,
+    )
+    .unwrap();
+
+    let config = Config::load(&path).unwrap();
+    assert!(config.icons.auto);
+    assert_eq!(
+        config.pass_icon("VIP", config_dir),
+        Some(PathBuf::from("icons/passes/VIP.png"))
+    );
+    assert_eq!(
+        config.pass_icon("Explicit", config_dir),
+        Some(PathBuf::from("explicit.png"))
+    );
+    assert_eq!(
+        config.badge_icon("Welcome", config_dir),
+        Some(PathBuf::from("icons/badges/Welcome.webp"))
+    );
+    assert_eq!(config.product_icon("Coins", config_dir), None);
+}
+
+#[test]
+fn auto_icons_disabled_by_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_dir = dir.path();
+
+    std::fs::create_dir_all(config_dir.join("icons/passes")).unwrap();
+    std::fs::write(config_dir.join("icons/passes/VIP.png"), b"fake").unwrap();
+
+    let path = config_dir.join("rbxsync.toml");
+    std::fs::write(
+        &path,
+        r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[passes.VIP]
+price = 100
+"#,
+    )
+    .unwrap();
+
+    let config = Config::load(&path).unwrap();
+    assert!(!config.icons.auto);
+    assert_eq!(config.pass_icon("VIP", config_dir), None);
+}
@@ -22,6 +22,7 @@ fn make_config(
         },
         codegen: Default::default(),
         icons: Default::default(),
+        guardrails: Default::default(),
         passes,
         badges,
         products,
@@ -43,6 +44,8 @@ fn new_pass_creates() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -68,6 +71,8 @@ fn same_pass_skips() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -108,6 +113,8 @@ fn changed_pass_price_updates() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -153,6 +160,8 @@ fn changed_pass_description_updates() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -202,6 +211,8 @@ fn changed_pass_icon_updates() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -272,6 +283,8 @@ fn has_changes_all_skip() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -311,6 +324,8 @@ fn has_changes_with_create() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -336,6 +351,8 @@ fn summary_counts() {
                     for_sale: true,
                     regional_pricing: false,
                     path: None,
+                    tags: Vec::new(),
+                    data: BTreeMap::new(),
                 },
             ),
             (
@@ -348,6 +365,8 @@ fn summary_counts() {
                     for_sale: true,
                     regional_pricing: false,
                     path: None,
+                    tags: Vec::new(),
+                    data: BTreeMap::new(),
                 },
             ),
             (
@@ -360,6 +379,8 @@ fn summary_counts() {
                     for_sale: true,
                     regional_pricing: false,
                     path: None,
+                    tags: Vec::new(),
+                    data: BTreeMap::new(),
                 },
             ),
         ]),
@@ -419,6 +440,8 @@ fn new_badge_creates() {
                 icon: None,
                 enabled: true,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -442,6 +465,8 @@ fn badge_description_change_updates() {
                 icon: None,
                 enabled: true,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -482,6 +507,8 @@ fn badge_enabled_change_updates() {
                 icon: None,
                 enabled: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -525,6 +552,8 @@ fn badge_icon_change_updates() {
                 icon: Some("badge.png".into()),
                 enabled: true,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -571,6 +600,9 @@ fn new_product_creates() {
                 regional_pricing: false,
                 store_page: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
+                schedule: Vec::new(),
             },
         )]),
     );
@@ -597,6 +629,9 @@ fn product_price_change_updates() {
                 regional_pricing: false,
                 store_page: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
+                schedule: Vec::new(),
             },
         )]),
     );
@@ -646,6 +681,9 @@ fn product_icon_change_updates() {
                 regional_pricing: false,
                 store_page: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
+                schedule: Vec::new(),
             },
         )]),
     );
@@ -691,6 +729,8 @@ fn pass_name_change_updates() {
                 for_sale: true,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -737,6 +777,8 @@ fn pass_for_sale_change_updates() {
                 for_sale: false,
                 regional_pricing: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -781,6 +823,8 @@ fn pass_regional_pricing_change_updates() {
                 for_sale: true,
                 regional_pricing: true,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -824,6 +868,8 @@ fn badge_name_change_updates() {
                 icon: None,
                 enabled: true,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
             },
         )]),
         BTreeMap::new(),
@@ -870,6 +916,9 @@ fn product_for_sale_change_updates() {
                 regional_pricing: false,
                 store_page: false,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
+                schedule: Vec::new(),
             },
         )]),
     );
@@ -916,6 +965,9 @@ fn product_store_page_change_updates() {
                 regional_pricing: false,
                 store_page: true,
                 path: None,
+                tags: Vec::new(),
+                data: BTreeMap::new(),
+                schedule: Vec::new(),
             },
         )]),
     );
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use rbxsync::config::Config;
+use rbxsync::diff::build_sync_plan;
+use rbxsync::guardrails::{
+    badge_cost, badge_costs, check_badge_budget, price_violations, BADGE_COST,
+};
+use rbxsync::lockfile::{Lockfile, PassLock, ProductLock};
+use rbxsync::schedule::{self, parse_time};
+
+fn lockfile() -> Lockfile {
+    Lockfile {
+        passes: BTreeMap::from([(
+            "VIP".into(),
+            PassLock {
+                id: 1,
+                name: "VIP".into(),
+                price: Some(100),
+                description: None,
+                icon_asset_id: None,
+                icon_hash: None,
+                for_sale: true,
+                regional_pricing: false,
+            },
+        )]),
+        products: BTreeMap::from([(
+            "Coins".into(),
+            ProductLock {
+                id: 2,
+                name: "Coins".into(),
+                price: 499,
+                description: None,
+                icon_asset_id: None,
+                icon_hash: None,
+                for_sale: true,
+                regional_pricing: false,
+                store_page: false,
+            },
+        )]),
+        ..Default::default()
+    }
+}
+
+fn violations(guardrails: &str, pass_price: u64, product_price: u64) -> Vec<String> {
+    let config: Config = toml::from_str(&format!(
+        r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[guardrails]
+{guardrails}
+
+[passes.VIP]
+price = {pass_price}
+
+[products.Coins]
+price = {product_price}
+"#
+    ))
+    .unwrap();
+    let lockfile = lockfile();
+    let plan = build_sync_plan(&config, &lockfile, Path::new(".")).unwrap();
+    price_violations(&plan, &config, &lockfile)
+        .iter()
+        .map(|v| format!("{v} overridable={}", v.overridable))
+        .collect()
+}
+
+#[test]
+fn no_guardrails_allow_any_change() {
+    assert!(violations("", 10_000, 4999).is_empty());
+}
+
+#[test]
+fn percent_threshold() {
+    let config = "max_price_change_percent = 50";
+    assert!(violations(config, 150, 748).is_empty());
+    assert_eq!(
+        violations(config, 100, 4999),
+        vec![
+            "product 'Coins': price 499 -> 4999 changes by 901% (max_price_change_percent = 50) overridable=true"
+        ]
+    );
+}
+
+#[test]
+fn absolute_threshold() {
+    let config = "max_price_change = 200";
+    assert!(violations(config, 300, 299).is_empty());
+    assert_eq!(
+        violations(config, 301, 499),
+        vec![
+            "pass 'VIP': price 100 -> 301 changes by 201 Robux (max_price_change = 200) overridable=true"
+        ]
+    );
+}
+
+#[test]
+fn pass_price_increases_can_be_forbidden() {
+    let config = "forbid_pass_price_increase = true\nmax_price_change = 1000";
+    assert!(violations(config, 50, 499).is_empty());
+    assert_eq!(
+        violations(config, 101, 499),
+        vec![
+            "pass 'VIP': price 100 -> 101 raises a pass price (forbid_pass_price_increase = true) overridable=false"
+        ]
+    );
+}
+
+#[test]
+fn schedule_transitions_skip_the_thresholds() {
+    let content = format!(
+        r#"{}
+[guardrails]
+max_price_change_percent = 50
+
+[products.Coins]
+price = 99
+
+[[products.Coins.schedule]]
+start = 2026-10-31T00:00:00Z
+end = 2026-11-02T00:00:00Z
+price = 49
+"#,
+        config_without_guardrails()
+    );
+    let sale_start = parse_time("2026-10-31T00:00:00Z").unwrap();
+    let sale_end = parse_time("2026-11-02T00:00:00Z").unwrap();
+
+    let check = |synced_price, at| {
+        let mut config: Config = toml::from_str(&content).unwrap();
+        schedule::apply(&mut config, at).unwrap();
+        let mut lockfile = lockfile();
+        lockfile.passes.clear();
+        lockfile.products.get_mut("Coins").unwrap().price = synced_price;
+        let plan = build_sync_plan(&config, &lockfile, Path::new(".")).unwrap();
+        assert!(plan.has_changes());
+        price_violations(&plan, &config, &lockfile)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    // The sale starting (99 -> 49) and ending (49 -> 99)
+    assert!(check(99, sale_start).is_empty());
+    assert!(check(49, sale_end).is_empty());
+    // Price changes that don't come from the schedule are still checked
+    assert_eq!(
+        check(499, sale_end),
+        vec!["product 'Coins': price 499 -> 99 changes by 80% (max_price_change_percent = 50)"]
+    );
+}
+
+#[test]
+fn badge_costs_follow_the_free_quota() {
+    assert_eq!(badge_costs(3, 1, 100), vec![0, 100, 100]);
+    assert_eq!(badge_costs(2, 5, 100), vec![0, 0]);
+    assert!(badge_costs(0, 0, 100).is_empty());
+}
+
+#[test]
+fn badge_cost_can_be_overridden() {
+    let default: Config = toml::from_str(config_without_guardrails()).unwrap();
+    assert_eq!(badge_cost(&default), BADGE_COST);
+
+    let config: Config = toml::from_str(&format!(
+        "{}\n[guardrails]\nbadge_cost = 250\n",
+        config_without_guardrails()
+    ))
+    .unwrap();
+    assert_eq!(badge_cost(&config), 250);
+}
+
+#[test]
+fn badge_budget() {
+    let config: Config = toml::from_str(
+        r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[guardrails]
+max_badge_spend = 150
+"#,
+    )
+    .unwrap();
+
+    assert!(check_badge_budget(&config, 100).is_ok());
+    let err = check_badge_budget(&config, 200).unwrap_err().to_string();
+    assert!(err.contains("R$200, over max_badge_spend = 150"), "{err}");
+
+    let unlimited: Config = toml::from_str(config_without_guardrails()).unwrap();
+    assert!(check_badge_budget(&unlimited, 10_000).is_ok());
+}
+
+fn config_without_guardrails() -> &'static str {
+    r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+"#
+}
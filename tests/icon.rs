@@ -0,0 +1,325 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+use rbxsync::cli::{Cli, Commands};
+use rbxsync::commands::icons::{gc, report};
+use rbxsync::icon::{
+    apply_circle_mask, contact_sheet, lint_badge_safe_area, preview_tile, process_icon,
+    process_icons, visible_outside_circle, IconShape, PREVIEW_TILE_SIZE,
+};
+use rbxsync::lockfile::{Lockfile, PassLock, LOCKFILE_NAME};
+
+fn solid(w: u32, h: u32) -> RgbaImage {
+    RgbaImage::from_pixel(w, h, Rgba([255, 0, 0, 255]))
+}
+
+/// An opaque disc inscribed in a transparent square.
+fn disc(size: u32) -> RgbaImage {
+    let mut img = solid(size, size);
+    apply_circle_mask(&mut img);
+    img
+}
+
+#[test]
+fn circle_mask_clears_corners() {
+    let mut img = solid(100, 100);
+    apply_circle_mask(&mut img);
+
+    assert_eq!(img.get_pixel(0, 0)[3], 0);
+    assert_eq!(img.get_pixel(99, 0)[3], 0);
+    assert_eq!(img.get_pixel(0, 99)[3], 0);
+    assert_eq!(img.get_pixel(99, 99)[3], 0);
+    assert_eq!(img.get_pixel(50, 50)[3], 255);
+    assert_eq!(img.get_pixel(50, 0)[3], 255);
+}
+
+#[test]
+fn square_art_has_content_outside_circle() {
+    let img = solid(100, 100);
+    // Every pixel outside the inscribed circle is filled
+    assert_eq!(visible_outside_circle(&img, 127), 1.0);
+    assert!(lint_badge_safe_area(&img).is_some());
+}
+
+#[test]
+fn circular_art_passes_lint() {
+    let img = disc(100);
+    assert_eq!(visible_outside_circle(&img, 0), 0.0);
+    assert!(lint_badge_safe_area(&img).is_none());
+}
+
+/// A disc whose edge fades out over a couple of pixels, spilling faint
+/// coverage just past the inscribed circle.
+fn antialiased_disc(size: u32) -> RgbaImage {
+    let radius = size as f64 / 2.0;
+    RgbaImage::from_fn(size, size, |x, y| {
+        let dx = x as f64 + 0.5 - radius;
+        let dy = y as f64 + 0.5 - radius;
+        let dist = (dx * dx + dy * dy).sqrt();
+        let coverage = (radius + 0.5 - dist).clamp(0.0, 1.0);
+        Rgba([255, 0, 0, (coverage * 255.0).round() as u8])
+    })
+}
+
+#[test]
+fn antialiased_circular_art_passes_lint() {
+    let img = antialiased_disc(100);
+    assert!(visible_outside_circle(&img, 0) > 0.0);
+    assert_eq!(visible_outside_circle(&img, 127), 0.0);
+    assert!(lint_badge_safe_area(&img).is_none());
+}
+
+#[test]
+fn visible_outside_circle_ignores_faint_pixels() {
+    let img = RgbaImage::from_pixel(100, 100, Rgba([255, 0, 0, 16]));
+    assert_eq!(visible_outside_circle(&img, 32), 0.0);
+    assert_eq!(visible_outside_circle(&img, 0), 1.0);
+}
+
+#[test]
+fn transparent_image_passes_lint() {
+    let img = RgbaImage::new(64, 64);
+    assert_eq!(visible_outside_circle(&img, 0), 0.0);
+    assert!(lint_badge_safe_area(&img).is_none());
+}
+
+#[test]
+fn preview_tile_resizes_and_masks_badges() {
+    let img = DynamicImage::ImageRgba8(solid(512, 512));
+
+    let badge = preview_tile(&img, IconShape::Circle);
+    assert_eq!(badge.dimensions(), (PREVIEW_TILE_SIZE, PREVIEW_TILE_SIZE));
+    assert_eq!(badge.get_pixel(0, 0)[3], 0);
+
+    let pass = preview_tile(&img, IconShape::Square);
+    assert_eq!(pass.dimensions(), (PREVIEW_TILE_SIZE, PREVIEW_TILE_SIZE));
+    assert_eq!(pass.get_pixel(0, 0)[3], 255);
+}
+
+#[test]
+fn contact_sheet_grid_dimensions() {
+    let tiles: Vec<RgbaImage> = (0..7)
+        .map(|_| preview_tile(&DynamicImage::ImageRgba8(solid(8, 8)), IconShape::Square))
+        .collect();
+
+    let sheet = contact_sheet(&tiles, 3);
+    let cell = PREVIEW_TILE_SIZE + 10;
+    assert_eq!(sheet.dimensions(), (3 * cell + 10, 3 * cell + 10));
+
+    let single = contact_sheet(&tiles[..1], 6);
+    assert_eq!(single.dimensions(), (cell + 10, cell + 10));
+}
+
+#[test]
+fn report_flags_missing_changed_and_invalid_icons() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_dir = dir.path();
+
+    solid(32, 32).save(config_dir.join("vip.png")).unwrap();
+    solid(16, 16).save(config_dir.join("edited.png")).unwrap();
+    std::fs::write(config_dir.join("broken.png"), b"not an image").unwrap();
+
+    let config_path = config_dir.join("rbxsync.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[passes.VIP]
+icon = "vip.png"
+
+[passes.Broken]
+icon = "broken.png"
+
+[passes.Edited]
+icon = "edited.png"
+
+[passes.Gone]
+icon = "gone.png"
+
+[products.Coins]
+price = 10
+"#,
+    )
+    .unwrap();
+
+    let vip_hash = blake3::hash(&std::fs::read(config_dir.join("vip.png")).unwrap())
+        .to_hex()
+        .to_string();
+    let mut lockfile = Lockfile {
+        version: 1,
+        universe_id: 1,
+        ..Default::default()
+    };
+    lockfile.passes.insert(
+        "VIP".into(),
+        PassLock {
+            id: 1,
+            name: "VIP".into(),
+            price: None,
+            description: None,
+            icon_asset_id: Some(4242),
+            icon_hash: Some(vip_hash),
+            for_sale: true,
+            regional_pricing: false,
+        },
+    );
+    lockfile.passes.insert(
+        "Edited".into(),
+        PassLock {
+            id: 2,
+            name: "Edited".into(),
+            price: None,
+            description: None,
+            icon_asset_id: Some(4343),
+            icon_hash: Some("stale".into()),
+            for_sale: true,
+            regional_pricing: false,
+        },
+    );
+    lockfile.save(&config_dir.join(LOCKFILE_NAME)).unwrap();
+
+    let cli = Cli {
+        command: Commands::Check,
+        config: config_path,
+        api_key: None,
+        select: Vec::new(),
+    };
+    let output = config_dir.join("report");
+    report(&cli, &output).unwrap();
+
+    let html = std::fs::read_to_string(output.join("index.html")).unwrap();
+    assert!(html.contains("asset: 4242"));
+    assert!(html.contains("no icon"));
+    assert!(html.contains("invalid: "));
+    // Broken was never synced, Edited has a stale hash, VIP is unchanged
+    assert_eq!(html.matches("never synced").count(), 1);
+    assert_eq!(html.matches("changed since last sync").count(), 1);
+    assert_eq!(html.matches("icon file missing").count(), 1);
+    assert!(html.contains("empty missing"));
+    assert!(output.join("pass-1.png").exists());
+}
+
+fn gc_fixture(dir: &std::path::Path) -> Cli {
+    std::fs::create_dir_all(dir.join("icons/passes")).unwrap();
+    std::fs::write(dir.join("icons/vip.png"), b"referenced").unwrap();
+    std::fs::write(dir.join("icons/passes/Auto.png"), b"convention").unwrap();
+    std::fs::write(dir.join("icons/old.png"), b"orphan").unwrap();
+    std::fs::write(dir.join("icons/passes/Gone.jpg"), b"orphan2").unwrap();
+    std::fs::write(dir.join("icons/notes.txt"), b"not an image").unwrap();
+
+    let config_path = dir.join("rbxsync.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[icons]
+auto = true
+
+[passes.VIP]
+icon = "icons/vip.png"
+
+[passes.Auto]
+"#,
+    )
+    .unwrap();
+
+    Cli {
+        command: Commands::Check,
+        config: config_path,
+        api_key: None,
+        select: Vec::new(),
+    }
+}
+
+#[test]
+fn gc_dry_run_keeps_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let cli = gc_fixture(dir.path());
+
+    gc(&cli, true, true, None).unwrap();
+
+    assert!(dir.path().join("icons/old.png").exists());
+    assert!(dir.path().join("icons/passes/Gone.jpg").exists());
+}
+
+#[test]
+fn gc_deletes_only_orphans() {
+    let dir = tempfile::tempdir().unwrap();
+    let cli = gc_fixture(dir.path());
+
+    gc(&cli, false, true, None).unwrap();
+
+    assert!(dir.path().join("icons/vip.png").exists());
+    assert!(dir.path().join("icons/passes/Auto.png").exists());
+    assert!(dir.path().join("icons/notes.txt").exists());
+    assert!(!dir.path().join("icons/old.png").exists());
+    assert!(!dir.path().join("icons/passes/Gone.jpg").exists());
+}
+
+#[test]
+fn gc_moves_orphans() {
+    let dir = tempfile::tempdir().unwrap();
+    let cli = gc_fixture(dir.path());
+    let trash = dir.path().join("trash");
+
+    gc(&cli, false, false, Some(&trash)).unwrap();
+
+    assert!(!dir.path().join("icons/old.png").exists());
+    assert!(trash.join("old.png").exists());
+    assert!(trash.join("passes/Gone.jpg").exists());
+    assert!(dir.path().join("icons/vip.png").exists());
+}
+
+#[test]
+fn gc_move_to_is_relative_to_config_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("sub");
+    let cli = gc_fixture(&project);
+    let relative = std::path::Path::new("icons/trash");
+
+    gc(&cli, false, false, Some(relative)).unwrap();
+
+    let trash = project.join("icons/trash");
+    assert!(trash.join("old.png").exists());
+    assert!(trash.join("passes/Gone.jpg").exists());
+    assert!(!dir.path().join("icons").exists());
+
+    // Icons already in the destination are not picked up again
+    gc(&cli, false, false, Some(relative)).unwrap();
+    assert!(trash.join("old.png").exists());
+    assert!(!trash.join("trash").exists());
+}
+
+#[test]
+fn process_icons_matches_sequential_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let paths: Vec<std::path::PathBuf> = (0..5u8)
+        .map(|i| {
+            let path = dir.path().join(format!("icon{i}.png"));
+            let mut img = RgbaImage::new(8 + i as u32, 8);
+            img.put_pixel(0, 0, Rgba([i * 40, 0, 0, 255]));
+            img.save(&path).unwrap();
+            path
+        })
+        .collect();
+
+    let parallel = process_icons(&paths, true).unwrap();
+    let sequential: Vec<Vec<u8>> = paths
+        .iter()
+        .map(|p| process_icon(p, true).unwrap())
+        .collect();
+    assert_eq!(parallel, sequential);
+
+    assert!(process_icons(&[], true).unwrap().is_empty());
+    assert!(process_icons(&[dir.path().join("missing.png")], true).is_err());
+}
@@ -19,6 +19,7 @@ fn make_cli(config_path: PathBuf) -> Cli {
         command: rbxsync::cli::Commands::Check, // unused by rename
         config: config_path,
         api_key: None,
+        select: Vec::new(),
     }
 }
 
@@ -258,3 +259,19 @@ price = 99
     assert!(lock.products.contains_key("coins_100"));
     assert_eq!(lock.products["coins_100"].id, 55);
 }
+
+#[test]
+fn rename_rejects_select() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = write_config(dir.path(), base_config());
+    write_lockfile(dir.path(), &base_lockfile());
+
+    let mut cli = make_cli(config_path);
+    cli.select = vec!["tag:shop".into()];
+    let err =
+        rbxsync::commands::rename::run(&cli, ResourceType::Passes, "VIP", "vip_pass").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "--select isn't supported by `rbxsync rename`"
+    );
+}
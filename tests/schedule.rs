@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+
+use rbxsync::config::Config;
+use rbxsync::diff::{build_sync_plan, Action};
+use rbxsync::lockfile::{Lockfile, ProductLock};
+use rbxsync::schedule::{self, parse_time, unix_time, upcoming_transitions};
+
+const HALLOWEEN_START: i64 = 1_793_404_800; // 2026-10-31T00:00:00Z
+const HALLOWEEN_END: i64 = 1_793_577_600; // 2026-11-02T00:00:00Z
+
+const SCHEDULE_CONFIG: &str = r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[products.Coins]
+price = 100
+
+[[products.Coins.schedule]]
+start = 2026-10-31T00:00:00Z
+end = 2026-11-02T00:00:00Z
+price = 50
+
+[[products.Coins.schedule]]
+start = 2026-12-24T00:00:00-05:00
+end = 2026-12-26T00:00:00-05:00
+for_sale = false
+
+[products.Gems]
+price = 200
+"#;
+
+fn write_config(dir: &std::path::Path, content: &str) -> std::path::PathBuf {
+    let path = dir.join("rbxsync.toml");
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn synced_lockfile() -> Lockfile {
+    let product = |id, price| ProductLock {
+        id,
+        name: String::new(),
+        price,
+        description: None,
+        icon_asset_id: None,
+        icon_hash: None,
+        for_sale: true,
+        regional_pricing: false,
+        store_page: false,
+    };
+    Lockfile {
+        products: BTreeMap::from([
+            (
+                "Coins".into(),
+                ProductLock {
+                    name: "Coins".into(),
+                    ..product(1, 100)
+                },
+            ),
+            (
+                "Gems".into(),
+                ProductLock {
+                    name: "Gems".into(),
+                    ..product(2, 200)
+                },
+            ),
+        ]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn unix_time_handles_offsets() {
+    assert_eq!(parse_time("1970-01-01T00:00:00Z").unwrap(), 0);
+    assert_eq!(parse_time("2000-02-29T12:30:00Z").unwrap(), 951_827_400);
+    assert_eq!(
+        parse_time("2026-10-31T02:00:00+02:00").unwrap(),
+        HALLOWEEN_START
+    );
+    assert!(parse_time("2026-10-31").is_err());
+    assert!(unix_time(&"2026-10-31T00:00:00".parse().unwrap()).is_err());
+}
+
+#[test]
+fn schedule_overrides_apply_inside_the_window() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = Config::load(&write_config(dir.path(), SCHEDULE_CONFIG)).unwrap();
+    let coins = &config.products["Coins"];
+
+    assert_eq!(
+        coins.effective_at(HALLOWEEN_START - 1).unwrap(),
+        (100, true)
+    );
+    assert_eq!(coins.effective_at(HALLOWEEN_START).unwrap(), (50, true));
+    assert_eq!(coins.effective_at(HALLOWEEN_END).unwrap(), (100, true));
+    let christmas = parse_time("2026-12-25T00:00:00Z").unwrap();
+    assert_eq!(coins.effective_at(christmas).unwrap(), (100, false));
+}
+
+#[test]
+fn sync_plan_applies_and_reverts_sales() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(dir.path(), SCHEDULE_CONFIG);
+
+    let mut config = Config::load(&path).unwrap();
+    schedule::apply(&mut config, HALLOWEEN_START + 60).unwrap();
+    let plan = build_sync_plan(&config, &synced_lockfile(), dir.path()).unwrap();
+    match &plan.products[0].action {
+        Action::Update { changes } => {
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].to_string(), "price: 100 -> 50");
+        }
+        other => panic!("expected Update, got {:?}", other),
+    }
+    assert!(matches!(plan.products[1].action, Action::Skip));
+
+    let mut config = Config::load(&path).unwrap();
+    schedule::apply(&mut config, HALLOWEEN_END).unwrap();
+    let plan = build_sync_plan(&config, &synced_lockfile(), dir.path()).unwrap();
+    assert!(!plan.has_changes());
+}
+
+#[test]
+fn upcoming_transitions_are_chronological() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = Config::load(&write_config(dir.path(), SCHEDULE_CONFIG)).unwrap();
+
+    let transitions = upcoming_transitions(&config, HALLOWEEN_START).unwrap();
+    let summary: Vec<_> = transitions
+        .iter()
+        .map(|t| format!("{} {} {} {}", t.time, t.key, t.starts, t.overrides))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            "2026-11-02T00:00:00Z Coins false price 50",
+            "2026-12-24T00:00:00-05:00 Coins true for_sale false",
+            "2026-12-26T00:00:00-05:00 Coins false for_sale false",
+        ]
+    );
+}
+
+#[test]
+fn invalid_schedules_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = SCHEDULE_CONFIG.split("[products.Gems]").next().unwrap();
+    let cases = [
+        (
+            "start = 2026-11-01T00:00:00Z\nend = 2026-11-03T00:00:00Z\nprice = 10",
+            "overlap",
+        ),
+        (
+            "start = 2027-01-02T00:00:00Z\nend = 2027-01-01T00:00:00Z\nprice = 10",
+            "must end",
+        ),
+        (
+            "start = 2027-01-01T00:00:00Z\nend = 2027-01-02T00:00:00Z",
+            "overrides nothing",
+        ),
+        (
+            "start = 2027-01-01T00:00:00\nend = 2027-01-02T00:00:00Z\nprice = 10",
+            "UTC offset",
+        ),
+    ];
+    for (entry, expected) in cases {
+        let content = format!("{base}\n[[products.Coins.schedule]]\n{entry}\n");
+        let err = Config::load(&write_config(dir.path(), &content)).unwrap_err();
+        assert!(format!("{err:#}").contains(expected), "{err:#}");
+    }
+}
@@ -0,0 +1,101 @@
+use rbxsync::select::{glob_match, Section, Selection, Selector};
+
+fn tags(tags: &[&str]) -> Vec<String> {
+    tags.iter().map(|t| t.to_string()).collect()
+}
+
+#[test]
+fn glob_patterns() {
+    assert!(glob_match("Coins*", "Coins100"));
+    assert!(glob_match("Coins*", "Coins"));
+    assert!(glob_match("*100", "Coins100"));
+    assert!(glob_match("C?ins*0", "Coins100"));
+    assert!(glob_match("*e*n", "Halloween"));
+    assert!(!glob_match("Coins?", "Coins"));
+    assert!(!glob_match("VIP", "VIPGold"));
+}
+
+#[test]
+fn empty_selection_matches_everything() {
+    let selection = Selection::parse_all(&[]).unwrap();
+    assert!(selection.is_empty());
+    assert!(selection.matches(Section::Badges, "Welcome", &[]));
+}
+
+#[test]
+fn terms_and_alternatives() {
+    let selection: Selection = "tag:event:halloween,!type:badges | VIP*".parse().unwrap();
+    let halloween = tags(&["event:halloween", "shop"]);
+
+    assert!(selection.matches(Section::Products, "Candy", &halloween));
+    assert!(!selection.matches(Section::Badges, "Candy", &halloween));
+    assert!(!selection.matches(Section::Products, "Coins", &tags(&["shop"])));
+    assert!(selection.matches(Section::Passes, "VIPGold", &[]));
+}
+
+#[test]
+fn repeated_selects_are_alternatives() {
+    let selection =
+        Selection::parse_all(&["type:pass".to_string(), "key:Coins*".to_string()]).unwrap();
+    assert!(selection.matches(Section::Passes, "VIP", &[]));
+    assert!(selection.matches(Section::Products, "Coins100", &[]));
+    assert!(!selection.matches(Section::Products, "Gems", &[]));
+}
+
+#[test]
+fn invalid_terms_are_rejected() {
+    let err = "type:gamepasses".parse::<Selection>().unwrap_err();
+    assert!(err.to_string().contains("Unknown type 'gamepasses'"));
+    assert!("tag:".parse::<Selection>().is_err());
+    assert!("VIP,".parse::<Selection>().is_err());
+}
+
+#[test]
+fn positional_selectors() {
+    let selectors =
+        Selector::parse_all(&["products.Coins*".to_string(), "pass.VIP".to_string()]).unwrap();
+    assert_eq!(selectors[1].to_string(), "passes.VIP");
+    assert!(selectors[0].matches(Section::Products, "Coins100"));
+    assert!(!selectors[0].matches(Section::Passes, "Coins100"));
+
+    let selection = Selection::default().with_selectors(&selectors);
+    assert!(selection.matches(Section::Products, "Coins100", &[]));
+    assert!(selection.matches(Section::Passes, "VIP", &[]));
+    assert!(!selection.matches(Section::Passes, "VIPGold", &[]));
+}
+
+#[test]
+fn selectors_narrow_select_expressions() {
+    let selectors = Selector::parse_all(&["products.*".to_string()]).unwrap();
+    let selection = Selection::parse_all(&["tag:sale".to_string()])
+        .unwrap()
+        .with_selectors(&selectors);
+    let sale = tags(&["sale"]);
+
+    assert!(selection.matches(Section::Products, "Coins100", &sale));
+    assert!(!selection.matches(Section::Products, "Coins100", &[]));
+    assert!(!selection.matches(Section::Passes, "VIP", &sale));
+}
+
+#[test]
+fn invalid_selectors_are_rejected() {
+    for selector in ["VIP", "gamepasses.VIP", "products.", "extra.legacy"] {
+        let err = selector.parse::<Selector>().unwrap_err();
+        assert!(err.to_string().contains("Invalid selector"), "{selector}");
+    }
+}
+
+#[test]
+fn and_requires_both_selections() {
+    let left: Selection = "tag:shop | VIP".parse().unwrap();
+    let both = left.and("type:products".parse().unwrap());
+    assert_eq!(
+        both.to_string(),
+        "tag:shop,type:products | key:VIP,type:products"
+    );
+    assert!(both.matches(Section::Products, "Coins", &tags(&["shop"])));
+    assert!(!both.matches(Section::Passes, "VIP", &[]));
+
+    let unchanged = Selection::default().and("VIP".parse().unwrap());
+    assert_eq!(unchanged.to_string(), "key:VIP");
+}
@@ -0,0 +1,145 @@
+//! The original per-pixel alpha bleed, kept as a reference to check that
+//! `rbxsync::alpha_bleed` produces identical output.
+
+use std::collections::VecDeque;
+
+use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
+
+pub fn alpha_bleed(img: &mut DynamicImage) {
+    let (w, h) = img.dimensions();
+
+    let mut can_be_sampled = vec![false; (w * h) as usize];
+    let mut visited = vec![false; (w * h) as usize];
+    let mut to_visit = VecDeque::new();
+    let index = |x: u32, y: u32| (x + y * w) as usize;
+
+    let adjacent_positions = |x, y| {
+        DIRECTIONS.iter().filter_map(move |(x_offset, y_offset)| {
+            let x_source = (x as i32) + x_offset;
+            let y_source = (y as i32) + y_offset;
+
+            if x_source < 0 || y_source < 0 || x_source >= w as i32 || y_source >= h as i32 {
+                return None;
+            }
+
+            Some((x_source as u32, y_source as u32))
+        })
+    };
+
+    for y in 0..h {
+        for x in 0..w {
+            let pixel = img.get_pixel(x, y);
+
+            if pixel[3] != 0 {
+                can_be_sampled[index(x, y)] = true;
+                visited[index(x, y)] = true;
+                continue;
+            }
+
+            let borders_opaque = adjacent_positions(x, y).any(|(x_source, y_source)| {
+                let source = img.get_pixel(x_source, y_source);
+                source[3] != 0
+            });
+
+            if borders_opaque {
+                visited[index(x, y)] = true;
+                to_visit.push_back((x, y));
+            }
+        }
+    }
+
+    loop {
+        let queue_length = to_visit.len();
+        if queue_length == 0 {
+            break;
+        }
+
+        let mut mutated_coords: Vec<(u32, u32)> = Vec::with_capacity(queue_length);
+
+        for _ in 0..queue_length {
+            if let Some((x, y)) = to_visit.pop_front() {
+                let mut new_color = (0u16, 0u16, 0u16);
+                let mut contributing = 0u16;
+
+                for (x_source, y_source) in adjacent_positions(x, y) {
+                    if can_be_sampled[index(x_source, y_source)] {
+                        let source = img.get_pixel(x_source, y_source);
+                        contributing += 1;
+                        new_color.0 += source[0] as u16;
+                        new_color.1 += source[1] as u16;
+                        new_color.2 += source[2] as u16;
+                    } else if !visited[index(x_source, y_source)] {
+                        visited[index(x_source, y_source)] = true;
+                        to_visit.push_back((x_source, y_source));
+                    }
+                }
+
+                let denominator = u16::max(1, contributing);
+                let pixel = Rgba([
+                    (new_color.0 / denominator) as u8,
+                    (new_color.1 / denominator) as u8,
+                    (new_color.2 / denominator) as u8,
+                    0,
+                ]);
+
+                img.put_pixel(x, y, pixel);
+                mutated_coords.push((x, y));
+            }
+        }
+
+        for (x, y) in mutated_coords {
+            can_be_sampled[index(x, y)] = true;
+        }
+    }
+}
+
+const DIRECTIONS: &[(i32, i32)] = &[
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// Deterministic test image: a few opaque blobs and noise on a transparent background.
+pub fn sample_image(w: u32, h: u32, seed: u64) -> image::RgbaImage {
+    let mut state = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let blobs: Vec<(f64, f64, f64)> = (0..4)
+        .map(|_| {
+            (
+                (next() % w as u64) as f64,
+                (next() % h as u64) as f64,
+                (w.min(h) as u64 / 8 + next() % (w.min(h) as u64 / 4 + 1)) as f64,
+            )
+        })
+        .collect();
+
+    image::RgbaImage::from_fn(w, h, |x, y| {
+        let noise = next();
+        let inside = blobs.iter().any(|&(cx, cy, r)| {
+            let (dx, dy) = (x as f64 - cx, y as f64 - cy);
+            dx * dx + dy * dy <= r * r
+        });
+        let alpha = if inside || noise % 97 == 0 {
+            (noise >> 8) as u8 | 1
+        } else {
+            0
+        };
+        Rgba([
+            (noise >> 16) as u8,
+            (noise >> 24) as u8,
+            (noise >> 32) as u8,
+            alpha,
+        ])
+    })
+}
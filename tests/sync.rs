@@ -0,0 +1,108 @@
+use rbxsync::commands::sync::confirm_actions;
+use rbxsync::config::Config;
+use rbxsync::diff::{Action, FieldChange, ResourceAction, SyncPlan};
+use rbxsync::guardrails::check_price_changes;
+use rbxsync::lockfile::{Lockfile, ProductLock};
+
+fn plan() -> SyncPlan {
+    let action = |name: &str, action| ResourceAction {
+        name: name.into(),
+        action,
+    };
+    SyncPlan {
+        passes: vec![
+            action("VIP", Action::Create),
+            action("Unchanged", Action::Skip),
+        ],
+        badges: vec![action("Welcome", Action::Create)],
+        products: vec![action(
+            "Coins",
+            Action::Update {
+                changes: vec![FieldChange {
+                    field: "price".into(),
+                    old: "499".into(),
+                    new: "4999".into(),
+                }],
+            },
+        )],
+        warnings: Vec::new(),
+    }
+}
+
+fn names(actions: &[ResourceAction]) -> Vec<&str> {
+    actions.iter().map(|a| a.name.as_str()).collect()
+}
+
+#[test]
+fn skipped_actions_are_dropped_from_the_plan() {
+    let mut plan = plan();
+    let mut output = Vec::new();
+    // An unknown answer asks again.
+    let confirmed =
+        confirm_actions(&mut plan, &mut "y\nmaybe\ns\nyes\n".as_bytes(), &mut output).unwrap();
+
+    assert!(confirmed);
+    assert_eq!(names(&plan.passes), vec!["VIP", "Unchanged"]);
+    assert!(plan.badges.is_empty());
+    assert_eq!(names(&plan.products), vec!["Coins"]);
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output.matches("Apply?").count(), 4);
+    assert!(output.contains("price: 499 -> 4999"));
+    assert!(!output.contains("Unchanged"));
+}
+
+#[test]
+fn abort_and_end_of_input_stop_the_sync() {
+    let mut plan = plan();
+    assert!(!confirm_actions(&mut plan, &mut "y\na\n".as_bytes(), &mut Vec::new()).unwrap());
+    assert!(!confirm_actions(&mut plan, &mut "y\n".as_bytes(), &mut Vec::new()).unwrap());
+}
+
+#[test]
+fn declining_a_blocked_price_change_lets_the_rest_apply() {
+    let config: Config = toml::from_str(
+        r#"
+[experience]
+universe_id = 1
+
+[experience.creator]
+type = "user"
+id = 1
+
+[guardrails]
+max_price_change_percent = 50
+
+[products.Coins]
+price = 4999
+"#,
+    )
+    .unwrap();
+    let mut lockfile = Lockfile::default();
+    lockfile.products.insert(
+        "Coins".into(),
+        ProductLock {
+            id: 1,
+            name: "Coins".into(),
+            price: 499,
+            description: None,
+            icon_asset_id: None,
+            icon_hash: None,
+            for_sale: true,
+            regional_pricing: false,
+            store_page: false,
+        },
+    );
+
+    let mut plan = plan();
+    assert!(check_price_changes(&plan, &config, &lockfile, false).is_err());
+
+    // Skip the Coins price change, apply the rest.
+    let confirmed =
+        confirm_actions(&mut plan, &mut "y\ny\ns\n".as_bytes(), &mut Vec::new()).unwrap();
+    assert!(confirmed);
+    check_price_changes(&plan, &config, &lockfile, false).unwrap();
+    assert_eq!(names(&plan.passes), vec!["VIP", "Unchanged"]);
+    assert_eq!(names(&plan.badges), vec!["Welcome"]);
+    assert!(plan.products.is_empty());
+}